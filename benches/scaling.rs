@@ -0,0 +1,224 @@
+//! How the maximum-line queries scale with the number of points, for random, gridded and
+//! heavily collinear inputs, in time and heap allocations per call. `max_collinear_line`
+//! is the per-point grouping that finds the members of every candidate line;
+//! `max_collinear_points` only counts them.
+//!
+//! Run with `cargo bench --bench scaling`. Arguments after `--` are `--max-n N` to stop
+//! at `N` points, `--budget SECONDS` to skip sizes expected to take longer than that per
+//! call (10 by default), and a substring of the workload names to run only those.
+
+use max_points_on_one_line::{
+    generate, max_collinear_count, max_collinear_line, max_collinear_points, Duplicates, Point,
+    SplitMix64, Workload,
+};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The system allocator, counting allocations and the bytes they ask for.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const SIZES: [usize; 5] = [10, 100, 1_000, 10_000, 100_000];
+
+/// Small sizes are repeated for at least this long, to average out timer resolution.
+const MIN_TIME: Duration = Duration::from_millis(200);
+
+/// The mean cost of one call.
+struct Measurement {
+    time: Duration,
+    allocations: usize,
+    bytes: usize,
+}
+
+/// Calls `run` on fresh inputs from `setup` until `MIN_TIME` has passed, timing and
+/// counting only `run`, and checks every answer against `expected`.
+fn measure<I>(setup: impl Fn() -> I, run: impl Fn(I) -> usize, expected: usize) -> Measurement {
+    let (mut time, mut allocations, mut bytes) = (Duration::ZERO, 0, 0);
+    let mut iterations = 0;
+    while time < MIN_TIME {
+        let input = setup();
+        let (before_allocations, before_bytes) = (
+            ALLOCATIONS.load(Ordering::Relaxed),
+            BYTES.load(Ordering::Relaxed),
+        );
+        let start = Instant::now();
+        let answer = black_box(run(black_box(input)));
+        time += start.elapsed();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before_allocations;
+        bytes += BYTES.load(Ordering::Relaxed) - before_bytes;
+        iterations += 1;
+        assert_eq!(answer, expected, "wrong answer");
+    }
+    Measurement {
+        time: time / iterations as u32,
+        allocations: allocations / iterations,
+        bytes: bytes / iterations,
+    }
+}
+
+/// About `n` points of the workload called `name`, and their answer if known.
+fn workload(name: &str, n: usize, rng: &mut SplitMix64) -> (Vec<Point<i32>>, Option<usize>) {
+    let instance = match name {
+        "random" => {
+            let range = n as i64;
+            let points = (0..n)
+                .map(|_| Point {
+                    x: rng.between(-range, range) as i32,
+                    y: rng.between(-range, range) as i32,
+                })
+                .collect();
+            return (points, None);
+        }
+        "grid" => {
+            let width = (n as f64).sqrt().round() as usize;
+            let height = n / width;
+            generate(Workload::Grid { width, height }, rng)
+        }
+        "collinear" => generate(
+            Workload::PlantedLine {
+                on_line: n - n / 10,
+                noise: n / 10,
+            },
+            rng,
+        ),
+        _ => unreachable!("unknown workload {}", name),
+    };
+    let points = instance
+        .points
+        .iter()
+        .map(|point| Point {
+            x: i32::try_from(point.x).expect("generated coordinates fit in i32"),
+            y: i32::try_from(point.y).expect("generated coordinates fit in i32"),
+        })
+        .collect();
+    (points, Some(instance.max))
+}
+
+/// The time for `size` points, extrapolated from the growth between the last two
+/// measurements in `history`, or cubically from a single one. `None` before any.
+fn estimate(history: &[(usize, Duration)], size: usize) -> Option<Duration> {
+    let &(last_size, last_time) = history.last()?;
+    let exponent = match history {
+        [.., (size_before, time_before), _] => {
+            let growth = (last_time.as_secs_f64() / time_before.as_secs_f64()).ln()
+                / (last_size as f64 / *size_before as f64).ln();
+            // Timer noise at small sizes can make the growth look slower than linear.
+            if growth.is_finite() {
+                growth.max(1.0)
+            } else {
+                3.0
+            }
+        }
+        _ => 3.0,
+    };
+    Some(last_time.mul_f64((size as f64 / last_size as f64).powf(exponent)))
+}
+
+fn main() {
+    let mut max_n = usize::MAX;
+    let mut budget = Duration::from_secs(10);
+    let mut filter = String::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().expect("option needs a value");
+        match arg.as_str() {
+            "--max-n" => max_n = value().parse().expect("--max-n takes a count"),
+            "--budget" => {
+                budget = Duration::from_secs_f64(value().parse().expect("--budget takes seconds"))
+            }
+            // Passed by `cargo bench`.
+            "--bench" => {}
+            _ => filter = arg,
+        }
+    }
+
+    println!(
+        "{:<10} {:<20} {:>7} {:>14} {:>13} {:>15}",
+        "workload", "query", "n", "time/call", "allocs/call", "bytes/call"
+    );
+    for name in ["random", "grid", "collinear"] {
+        if !name.contains(&filter) {
+            continue;
+        }
+        let mut rng = SplitMix64::new(0xbe9c);
+        // The (size, time) measurements of each query, to extrapolate from.
+        let mut history: [Vec<(usize, Duration)>; 2] = [Vec::new(), Vec::new()];
+        for n in SIZES.into_iter().filter(|&n| n <= max_n) {
+            // Without a known answer, the count-only query is the reference, computed only
+            // if something is measured.
+            let (points, mut reference) = workload(name, n, &mut rng);
+            for (query, history) in ["max_collinear_line", "max_collinear_points"]
+                .into_iter()
+                .zip(&mut history)
+            {
+                let size = points.len();
+                if let Some(estimate) = estimate(history, size) {
+                    if estimate > budget {
+                        println!(
+                            "{:<10} {:<20} {:>7} {:>14} {:>13}",
+                            name,
+                            query,
+                            size,
+                            format!("~{:.1?}", estimate),
+                            "skipped"
+                        );
+                        continue;
+                    }
+                }
+                let expected = *reference
+                    .get_or_insert_with(|| max_collinear_count(&points, Duplicates::Count));
+                let measurement = if query == "max_collinear_line" {
+                    measure(
+                        || &points[..],
+                        |points| {
+                            max_collinear_line(points, Duplicates::Count)
+                                .map_or(0, |best| best.count())
+                        },
+                        expected,
+                    )
+                } else {
+                    measure(
+                        || points.iter().map(|point| vec![point.x, point.y]).collect(),
+                        |rows| max_collinear_points(rows) as usize,
+                        expected,
+                    )
+                };
+                history.push((size, measurement.time));
+                println!(
+                    "{:<10} {:<20} {:>7} {:>14} {:>13} {:>15}",
+                    name,
+                    query,
+                    size,
+                    format!("{:.1?}", measurement.time),
+                    measurement.allocations,
+                    measurement.bytes
+                );
+            }
+        }
+    }
+}
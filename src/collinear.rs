@@ -0,0 +1,720 @@
+//! Queries for the lines through the most points of a set.
+
+use crate::geometry::{slope, Coordinate, Line, Point, Slope};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// The input points lying on one line, as indices into the input slice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollinearPoints<T: Coordinate> {
+    /// The line through every point in `indices`.
+    pub line: Line<T>,
+    /// Indices of the points on `line`, in increasing order.
+    pub indices: Vec<usize>,
+}
+
+impl<T: Coordinate> CollinearPoints<T> {
+    /// The number of points on the line.
+    pub fn count(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+/// How coincident input points are counted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Every copy of a point counts towards each line through it.
+    #[default]
+    Count,
+    /// The copies of a point count once, and are reported by their first index.
+    Merge,
+}
+
+/// The first copy of each distinct point, with its index into `points`, in input order.
+fn unique_points<T: Coordinate>(points: &[Point<T>]) -> (Vec<Point<T>>, Vec<usize>) {
+    let mut seen = HashSet::new();
+    (0..points.len())
+        .filter(|&index| seen.insert(&points[index]))
+        .map(|index| (points[index].clone(), index))
+        .unzip()
+}
+
+/// Runs `query` with duplicates treated as `duplicates` asks, reporting indices into
+/// `points` either way.
+fn with_duplicates<T: Coordinate>(
+    points: &[Point<T>],
+    duplicates: Duplicates,
+    query: impl FnOnce(&[Point<T>]) -> Vec<CollinearPoints<T>>,
+) -> Vec<CollinearPoints<T>> {
+    match duplicates {
+        Duplicates::Count => query(points),
+        Duplicates::Merge => {
+            let (unique_points, first_indices) = unique_points(points);
+            let mut lines = query(&unique_points);
+            for line in &mut lines {
+                for index in &mut line.indices {
+                    *index = first_indices[*index];
+                }
+            }
+            lines
+        }
+    }
+}
+
+/// For each input point, the line through it containing the most points.
+fn collinear_groups<T: Coordinate>(points: &[Point<T>]) -> Vec<CollinearPoints<T>> {
+    (0..points.len())
+        .map(|index| collinear_group(index, points))
+        .collect()
+}
+
+fn collinear_group<T: Coordinate>(index: usize, all_points: &[Point<T>]) -> CollinearPoints<T> {
+    let lines_containing_point = lines_containing(index, all_points);
+    let (line, indices) = match lines_containing_point
+        .into_iter()
+        .min_by(|p, q| q.1.len().cmp(&p.1.len()).then_with(|| p.0.cmp(&q.0)))
+    {
+        Some(line_and_indices) => line_and_indices,
+        None => (
+            Line::horizontal_through(&all_points[index]),
+            coincident_points(index, all_points).collect(),
+        ),
+    };
+    CollinearPoints { line, indices }
+}
+
+/// The indices of all copies of the point at `index`, itself included.
+fn coincident_points<'a, T: Coordinate>(
+    index: usize,
+    all_points: &'a [Point<T>],
+) -> impl Iterator<Item = usize> + 'a {
+    let point = &all_points[index];
+    (0..all_points.len()).filter(move |&other_index| all_points[other_index] == *point)
+}
+
+/// Every line through at least two distinct points, each reported once, in no particular
+/// order. If all points coincide, the single group of `collinear_group` is reported.
+fn distinct_lines<T: Coordinate>(points: &[Point<T>]) -> Vec<CollinearPoints<T>> {
+    let mut lines: Vec<CollinearPoints<T>> = (0..points.len())
+        .flat_map(|index| {
+            lines_containing(index, points)
+                .into_iter()
+                // Each line is reported only from its lowest-indexed point.
+                .filter(move |(_, indices)| indices[0] == index)
+                .map(|(line, indices)| CollinearPoints { line, indices })
+        })
+        .collect();
+    if lines.is_empty() && !points.is_empty() {
+        lines.push(collinear_group(0, points));
+    }
+    lines
+}
+
+/// Orders results from the most to the fewest points, breaking ties by the line.
+fn ranking<T: Coordinate>(p: &CollinearPoints<T>, q: &CollinearPoints<T>) -> Ordering {
+    q.count().cmp(&p.count()).then_with(|| p.line.cmp(&q.line))
+}
+
+/// The lines through the point at `index`, each with the sorted indices of its points.
+/// Copies of the point lie on every one of these lines.
+fn lines_containing<T: Coordinate>(
+    index: usize,
+    all_points: &[Point<T>],
+) -> HashMap<Line<T>, Vec<usize>> {
+    let point = &all_points[index];
+    let mut lines = HashMap::<Line<T>, Vec<usize>>::new();
+    all_points
+        .iter()
+        .enumerate()
+        .filter(|(_, other_point)| *other_point != point)
+        .for_each(|(other_index, other_point)| {
+            let line = Line::new(point, other_point);
+            lines.entry(line).or_default().push(other_index);
+        });
+    let copies: Vec<usize> = coincident_points(index, all_points).collect();
+    for indices in lines.values_mut() {
+        indices.extend_from_slice(&copies);
+        indices.sort_unstable();
+    }
+    lines
+}
+
+/// The line containing the most input points, together with those points, or `None`
+/// for an empty input. Points that all coincide are reported on the horizontal line
+/// through them.
+///
+/// When several lines share the maximum, the least one in the `Line` order is chosen.
+pub fn max_collinear_line<T: Coordinate>(
+    points: &[Point<T>],
+    duplicates: Duplicates,
+) -> Option<CollinearPoints<T>> {
+    with_duplicates(points, duplicates, |points| {
+        collinear_groups(points)
+            .into_iter()
+            .min_by(ranking)
+            .into_iter()
+            .collect()
+    })
+    .pop()
+}
+
+/// The number of input points on the line containing the most of them, or 0 for an empty
+/// input: the count of `max_collinear_line` without finding the line or its members.
+///
+/// Each point in turn anchors the lines to the points after it, counted by slope in one
+/// map reused across anchors, since the richest line is found from its lowest-indexed
+/// point. Anchors stop once the points from them on could not beat the best line so far.
+/// This takes O(n²) time and O(n) memory.
+pub fn max_collinear_count<T: Coordinate>(points: &[Point<T>], duplicates: Duplicates) -> usize {
+    let merged: Vec<Point<T>>;
+    let points = match duplicates {
+        Duplicates::Count => points,
+        Duplicates::Merge => {
+            merged = unique_points(points).0;
+            &merged
+        }
+    };
+    let mut best = 0;
+    let mut counts = HashMap::<Slope<T>, usize>::new();
+    for (index, point) in points.iter().enumerate() {
+        if points.len() - index <= best {
+            break;
+        }
+        let mut copies = 1;
+        let mut most = 0;
+        for other_point in &points[index + 1..] {
+            if other_point == point {
+                copies += 1;
+            } else {
+                let count = counts.entry(slope(point, other_point)).or_insert(0);
+                *count += 1;
+                most = most.max(*count);
+            }
+        }
+        best = best.max(copies + most);
+        counts.clear();
+    }
+    best
+}
+
+/// Every line containing the maximum number of input points, in the `Line` order.
+pub fn all_max_collinear_lines<T: Coordinate>(
+    points: &[Point<T>],
+    duplicates: Duplicates,
+) -> Vec<CollinearPoints<T>> {
+    with_duplicates(points, duplicates, |points| {
+        let mut lines = distinct_lines(points);
+        let max = lines.iter().map(CollinearPoints::count).max().unwrap_or(0);
+        lines.retain(|line| line.count() == max);
+        lines.sort_by(ranking);
+        lines
+    })
+}
+
+/// The `k` lines containing the most input points, from the richest down, with ties in
+/// the `Line` order. Each line appears once, however many of its points discover it.
+pub fn top_k_lines<T: Coordinate>(
+    points: &[Point<T>],
+    k: usize,
+    duplicates: Duplicates,
+) -> Vec<CollinearPoints<T>> {
+    with_duplicates(points, duplicates, |points| {
+        let mut lines = distinct_lines(points);
+        lines.sort_by(ranking);
+        lines.truncate(k);
+        lines
+    })
+}
+
+/// Every line containing at least `k` input points, each reported once, ordered like
+/// `top_k_lines`.
+///
+/// For `k > 2` the lines are found without listing every pair of points: a line with `k`
+/// points has its lowest-indexed point among the first `n - k + 1`, so only those serve
+/// as anchors, each anchor counts the lines to the points after it in one reused map, and
+/// member lists are built only for the lines that qualify. This takes O(n·(n - k + 1))
+/// time and O(n) memory beyond the output.
+pub fn rich_lines<T: Coordinate>(
+    points: &[Point<T>],
+    k: usize,
+    duplicates: Duplicates,
+) -> Vec<CollinearPoints<T>> {
+    with_duplicates(points, duplicates, |points| {
+        let all_coincide = points.iter().all(|point| *point == points[0]);
+        let mut lines = if k <= 2 || all_coincide {
+            let mut lines = distinct_lines(points);
+            lines.retain(|line| line.count() >= k);
+            lines
+        } else {
+            anchored_rich_lines(points, k)
+        };
+        lines.sort_by(ranking);
+        lines
+    })
+}
+
+fn anchored_rich_lines<T: Coordinate>(points: &[Point<T>], k: usize) -> Vec<CollinearPoints<T>> {
+    let mut lines = Vec::new();
+    let mut reported = HashSet::new();
+    let mut counts = HashMap::<Line<T>, usize>::new();
+    let anchors = (points.len() + 1).saturating_sub(k);
+    for (index, point) in points.iter().enumerate().take(anchors) {
+        // Every line through a point with an earlier copy was found from that copy.
+        if points[..index].contains(point) {
+            continue;
+        }
+        let copies: Vec<usize> = coincident_points(index, points).collect();
+        let later_points = || {
+            points
+                .iter()
+                .enumerate()
+                .skip(index + 1)
+                .filter(|(_, other_point)| *other_point != point)
+        };
+        for (_, other_point) in later_points() {
+            *counts
+                .entry(Line::new(point, other_point))
+                .or_insert(copies.len()) += 1;
+        }
+        // A line already reported passes through an earlier anchor.
+        let mut members: HashMap<Line<T>, Vec<usize>> = counts
+            .drain()
+            .filter(|(line, count)| *count >= k && !reported.contains(line))
+            .map(|(line, _)| (line, copies.clone()))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        for (other_index, other_point) in later_points() {
+            if let Some(indices) = members.get_mut(&Line::new(point, other_point)) {
+                indices.push(other_index);
+            }
+        }
+        for (line, mut indices) in members {
+            indices.sort_unstable();
+            reported.insert(line.clone());
+            lines.push(CollinearPoints { line, indices });
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::max_collinear_points;
+    use crate::random::SplitMix64;
+    use crate::rational::BigInt;
+    use crate::test_support::{cross, fixture, random_points};
+
+    #[test]
+    fn test_max_collinear_line() {
+        let points = fixture("[[1,1],[3,2],[5,3],[4,1],[2,3],[1,4]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(best.count(), 4);
+        assert_eq!(best.indices, vec![1, 3, 4, 5]);
+        assert_eq!(best.line.to_string(), "y = -1·x + 5");
+        assert!(best.indices.iter().all(|&i| best.line.contains(&points[i])));
+    }
+
+    #[test]
+    fn test_max_collinear_line_degenerate_inputs() {
+        assert_eq!(max_collinear_line::<i32>(&[], Duplicates::Count), None);
+        let best = max_collinear_line(&[Point { x: 4, y: -2 }], Duplicates::Count).unwrap();
+        assert_eq!(best.indices, vec![0]);
+        assert_eq!(best.line.to_string(), "y = -2");
+    }
+
+    #[test]
+    fn test_ties_are_broken_deterministically() {
+        let square = fixture("[[0,0],[1,0],[0,1],[1,1]]");
+        let all_max = all_max_collinear_lines(&square, Duplicates::Count);
+        let lines: Vec<String> = all_max.iter().map(|line| line.line.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "y = 1·x",
+                "y = 0",
+                "y = 1",
+                "x = 0",
+                "x = 1",
+                "y = -1·x + 1"
+            ]
+        );
+        assert!(all_max.windows(2).all(|pair| pair[0].line < pair[1].line));
+        let best = max_collinear_line(&square, Duplicates::Count).unwrap();
+        assert_eq!(best, all_max[0]);
+        assert_eq!(best.indices, vec![0, 3]);
+        // Neither repeated runs nor a reordered input change the chosen line.
+        for _ in 0..20 {
+            assert_eq!(
+                max_collinear_line(&square, Duplicates::Count).unwrap(),
+                best
+            );
+        }
+        let reversed: Vec<Point<i32>> = square.iter().rev().copied().collect();
+        assert_eq!(
+            max_collinear_line(&reversed, Duplicates::Count)
+                .unwrap()
+                .line,
+            best.line
+        );
+    }
+
+    #[test]
+    fn test_all_max_collinear_lines() {
+        let points = fixture("[[0,0],[1,1],[2,2],[0,2],[2,0],[5,5],[0,1]]");
+        let all_max = all_max_collinear_lines(&points, Duplicates::Count);
+        assert_eq!(all_max.len(), 1);
+        assert_eq!(all_max[0].indices, vec![0, 1, 2, 5]);
+        let points = fixture("[[0,0],[1,1],[2,2],[0,2],[0,4],[3,3],[0,6]]");
+        let all_max = all_max_collinear_lines(&points, Duplicates::Count);
+        let lines: Vec<String> = all_max.iter().map(|line| line.line.to_string()).collect();
+        assert_eq!(lines, ["y = 1·x", "x = 0"]);
+        assert_eq!(all_max[0].indices, vec![0, 1, 2, 5]);
+        assert_eq!(all_max[1].indices, vec![0, 3, 4, 6]);
+        assert_eq!(
+            all_max_collinear_lines::<i32>(&[], Duplicates::Count),
+            vec![]
+        );
+        assert_eq!(
+            all_max_collinear_lines(&[Point { x: 1, y: 2 }], Duplicates::Count),
+            vec![max_collinear_line(&[Point { x: 1, y: 2 }], Duplicates::Count).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_top_k_lines() {
+        let points = [
+            (0, 0),
+            (1, 1),
+            (2, 2),
+            (3, 3),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (2, 0),
+        ]
+        .map(|(x, y)| Point { x, y });
+        let top = top_k_lines(&points, 4, Duplicates::Count);
+        let summary: Vec<(String, usize)> = top
+            .iter()
+            .map(|line| (line.line.to_string(), line.count()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("y = 1·x".to_string(), 4),
+                ("x = 0".to_string(), 4),
+                ("y = 0".to_string(), 3),
+                ("y = -1·x + 2".to_string(), 3),
+            ]
+        );
+        assert_eq!(top[0].indices, vec![0, 1, 2, 3]);
+        assert_eq!(top_k_lines(&points, 0, Duplicates::Count), vec![]);
+        let all = top_k_lines(&points, usize::MAX, Duplicates::Count);
+        assert_eq!(all[..4], top[..]);
+        for (i, line) in all.iter().enumerate() {
+            assert!(line.indices.iter().all(|&j| line.line.contains(&points[j])));
+            assert!(all[i + 1..].iter().all(|other| other.line != line.line));
+        }
+        let pairs = all.iter().map(|line| line.count() * (line.count() - 1) / 2);
+        assert_eq!(pairs.sum::<usize>(), points.len() * (points.len() - 1) / 2);
+    }
+
+    #[test]
+    fn test_rich_lines() {
+        let points = [
+            (0, 0),
+            (1, 1),
+            (2, 2),
+            (3, 3),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (2, 0),
+        ]
+        .map(|(x, y)| Point { x, y });
+        let rich: Vec<String> = rich_lines(&points, 3, Duplicates::Count)
+            .iter()
+            .map(|line| line.line.to_string())
+            .collect();
+        assert_eq!(rich, ["y = 1·x", "x = 0", "y = 0", "y = -1·x + 2"]);
+        assert_eq!(
+            rich_lines(&points, 4, Duplicates::Count),
+            top_k_lines(&points, 2, Duplicates::Count)
+        );
+        assert_eq!(rich_lines(&points, 5, Duplicates::Count), vec![]);
+        assert_eq!(rich_lines(&points, 100, Duplicates::Count), vec![]);
+    }
+
+    #[test]
+    fn test_rich_lines_match_filtered_distinct_lines() {
+        let mut rng = SplitMix64::new(0x510e_527f_ade6_82d1);
+        for round in 0..200 {
+            // Small ranges produce plenty of duplicates, including all-coincident sets.
+            let range = if round % 4 == 0 { 1 } else { 3 };
+            let points = random_points(&mut rng, 1 + round % 25, range);
+            let all = top_k_lines(&points, usize::MAX, Duplicates::Count);
+            for k in 0..8 {
+                let expected: Vec<_> = all
+                    .iter()
+                    .filter(|line| line.count() >= k)
+                    .cloned()
+                    .collect();
+                assert_eq!(
+                    rich_lines(&points, k, Duplicates::Count),
+                    expected,
+                    "k = {}",
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_collinear_count() {
+        assert_eq!(max_collinear_count::<i32>(&[], Duplicates::Count), 0);
+        let points = fixture("[[1,1],[2,3],[1,1],[3,5],[2,3],[7,0]]");
+        assert_eq!(max_collinear_count(&points, Duplicates::Count), 5);
+        assert_eq!(max_collinear_count(&points, Duplicates::Merge), 3);
+        let mut rng = SplitMix64::new(0x3c6e_f372_fe94_f82b);
+        for round in 0..300 {
+            let range = if round % 3 == 0 { 2 } else { 20 };
+            let points = random_points(&mut rng, round % 30, range);
+            for duplicates in [Duplicates::Count, Duplicates::Merge] {
+                assert_eq!(
+                    max_collinear_count(&points, duplicates),
+                    max_collinear_line(&points, duplicates).map_or(0, |best| best.count()),
+                    "{:?}",
+                    points
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates_count_towards_every_line() {
+        assert_eq!(
+            max_collinear_points(vec![vec![1, 1], vec![1, 1], vec![2, 3]]),
+            3
+        );
+        assert_eq!(
+            max_collinear_points(vec![
+                vec![0, 0],
+                vec![1, 1],
+                vec![0, 0],
+                vec![5, 0],
+                vec![1, 1]
+            ]),
+            4
+        );
+        let points = fixture("[[1,1],[1,1],[2,3],[4,0]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(best.indices, vec![0, 1, 2]);
+        let all = top_k_lines(&points, usize::MAX, Duplicates::Count);
+        let counts: Vec<usize> = all.iter().map(CollinearPoints::count).collect();
+        assert_eq!(counts, [3, 3, 2]);
+        assert_eq!(rich_lines(&points, 3, Duplicates::Count), all[..2]);
+    }
+
+    #[test]
+    fn test_merged_duplicates_count_once() {
+        let points = fixture("[[1,1],[2,3],[1,1],[3,5],[2,3]]");
+        let best = max_collinear_line(&points, Duplicates::Merge).unwrap();
+        assert_eq!(best.indices, vec![0, 1, 3]);
+        assert_eq!(
+            max_collinear_line(&points, Duplicates::Count)
+                .unwrap()
+                .count(),
+            5
+        );
+        let points = fixture("[[1,1],[1,1],[2,3]]");
+        assert_eq!(
+            top_k_lines(&points, usize::MAX, Duplicates::Merge),
+            vec![CollinearPoints {
+                line: Line::new(&points[0], &points[2]),
+                indices: vec![0, 2],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_only_duplicates() {
+        let points = [Point { x: 5, y: -5 }; 4];
+        let expected = CollinearPoints {
+            line: Line::horizontal_through(&points[0]),
+            indices: vec![0, 1, 2, 3],
+        };
+        assert_eq!(
+            max_collinear_line(&points, Duplicates::Count),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            all_max_collinear_lines(&points, Duplicates::Count),
+            vec![expected.clone()]
+        );
+        assert_eq!(
+            top_k_lines(&points, 3, Duplicates::Count),
+            vec![expected.clone()]
+        );
+        assert_eq!(rich_lines(&points, 4, Duplicates::Count), vec![expected]);
+        assert_eq!(rich_lines(&points, 5, Duplicates::Count), vec![]);
+        let merged = CollinearPoints {
+            line: Line::horizontal_through(&points[0]),
+            indices: vec![0],
+        };
+        assert_eq!(
+            max_collinear_line(&points, Duplicates::Merge),
+            Some(merged.clone())
+        );
+        assert_eq!(rich_lines(&points, 1, Duplicates::Merge), vec![merged]);
+        assert_eq!(rich_lines(&points, 2, Duplicates::Merge), vec![]);
+        assert_eq!(max_collinear_points(vec![vec![7, 7]; 3]), 3);
+    }
+
+    #[test]
+    fn test_collinear_group_matches_brute_force() {
+        let mut rng = SplitMix64::new(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..200 {
+            let points = random_points(&mut rng, 15, 4);
+            for (index, p) in points.iter().enumerate() {
+                let group = collinear_group(index, &points);
+                assert!(group.indices.contains(&index));
+                for &q in &group.indices {
+                    assert!(group.line.contains(&points[q]));
+                    for &r in &group.indices {
+                        assert_eq!(cross(p, &points[q], &points[r]), 0);
+                    }
+                }
+                let brute_force = points
+                    .iter()
+                    .filter(|q| *q != p)
+                    .map(|q| points.iter().filter(|r| cross(p, q, r) == 0).count())
+                    .max()
+                    .unwrap_or_else(|| points.iter().filter(|q| *q == p).count());
+                assert_eq!(group.count(), brute_force);
+            }
+        }
+    }
+
+    fn max_collinear<T: Coordinate>(coordinates: &[(T, T)]) -> usize {
+        let points: Vec<Point<T>> = coordinates
+            .iter()
+            .map(|(x, y)| Point {
+                x: x.clone(),
+                y: y.clone(),
+            })
+            .collect();
+        collinear_groups(&points)
+            .iter()
+            .map(CollinearPoints::count)
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_narrow_coordinate_types() {
+        let coordinates = [(0, 0), (1, 1), (2, 2), (2, 0), (0, 2)];
+        assert_eq!(
+            max_collinear(&coordinates.map(|(x, y)| (x as i8, y as i8))),
+            3
+        );
+        assert_eq!(
+            max_collinear(&coordinates.map(|(x, y)| (x as i16, y as i16))),
+            3
+        );
+        assert_eq!(
+            max_collinear(&coordinates.map(|(x, y)| (x as u8, y as u8))),
+            3
+        );
+        assert_eq!(
+            max_collinear(&coordinates.map(|(x, y)| (x as u16, y as u16))),
+            3
+        );
+    }
+
+    #[test]
+    fn test_u32_extreme_coordinates() {
+        let max_points = max_collinear(&[
+            (0, u32::MAX),
+            (u32::MAX, 0),
+            (u32::MAX / 2, u32::MAX / 2 + 1),
+            (0, 0),
+            (u32::MAX, u32::MAX),
+            (1, 1),
+        ]);
+        assert_eq!(max_points, 3);
+    }
+
+    #[test]
+    fn test_i64_extreme_coordinates() {
+        let max_points = max_collinear(&[
+            (i64::MIN, i64::MIN),
+            (i64::MAX, i64::MAX),
+            (0, 0),
+            (-1, -1),
+            (i64::MAX, i64::MIN),
+            (i64::MIN, i64::MAX),
+            (0, -1),
+            (-1, 0),
+            (1, -2),
+        ]);
+        assert_eq!(max_points, 5);
+    }
+
+    #[test]
+    fn test_i128_extreme_coordinates() {
+        let max_points = max_collinear(&[
+            (i128::MIN, i128::MIN),
+            (i128::MAX, i128::MAX),
+            (0, 0),
+            (-1, -1),
+            (i128::MAX, i128::MIN),
+            (i128::MIN, i128::MAX),
+            (0, -1),
+            (-1, 0),
+            (1, -2),
+        ]);
+        assert_eq!(max_points, 5);
+    }
+
+    #[test]
+    fn test_u64_extreme_coordinates() {
+        let max_points = max_collinear(&[
+            (0, u64::MAX),
+            (u64::MAX, 0),
+            (u64::MAX / 2, u64::MAX / 2 + 1),
+            (1, u64::MAX - 1),
+            (0, 0),
+            (u64::MAX, u64::MAX),
+        ]);
+        assert_eq!(max_points, 4);
+    }
+
+    #[test]
+    fn test_forty_digit_coordinates() {
+        let parse = |x: &str, y: &str| (x.parse::<BigInt>().unwrap(), y.parse::<BigInt>().unwrap());
+        let (origin_x, origin_y) = parse(
+            "-3141592653589793238462643383279502884197",
+            "2718281828459045235360287471352662497757",
+        );
+        let (step_x, step_y) = parse(
+            "1000000000000000000000000000000000000007",
+            "-999999999999999999999999999999999999989",
+        );
+        let along = |k: i32, offset: i32| {
+            (
+                &origin_x + &(&BigInt::from(k) * &step_x),
+                &(&origin_y + &(&BigInt::from(k) * &step_y)) + &BigInt::from(offset),
+            )
+        };
+        let mut coordinates: Vec<(BigInt, BigInt)> = (0..5).map(|k| along(k, 0)).collect();
+        // A parallel line one unit above, and a point just off the main line.
+        coordinates.extend((0..4).map(|k| along(2 * k, 1)));
+        coordinates.push(along(7, -1));
+        assert_eq!(max_collinear(&coordinates), 5);
+    }
+}
@@ -0,0 +1,410 @@
+//! Point clouds in CSV or TSV files, where the coordinates are two of possibly many
+//! columns. The remaining columns travel with each point as metadata, and rows can be
+//! written back out exactly as they were read.
+
+use crate::geometry::{Coordinate, Point};
+use std::error::Error;
+use std::fmt;
+
+/// A column chosen by its header name or by its position, counted from 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The column whose header is this name.
+    Name(String),
+    /// The column at this position.
+    Index(usize),
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+/// How to read a table of points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The field separator, `,` for CSV and a tab for TSV.
+    pub delimiter: char,
+    /// Whether the first row names the columns.
+    pub header: bool,
+    /// The column holding x coordinates.
+    pub x: Column,
+    /// The column holding y coordinates.
+    pub y: Column,
+}
+
+impl CsvOptions {
+    /// Comma-separated values under a header row.
+    pub fn csv(x: impl Into<Column>, y: impl Into<Column>) -> Self {
+        CsvOptions {
+            delimiter: ',',
+            header: true,
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Tab-separated values under a header row.
+    pub fn tsv(x: impl Into<Column>, y: impl Into<Column>) -> Self {
+        CsvOptions {
+            delimiter: '\t',
+            ..Self::csv(x, y)
+        }
+    }
+}
+
+impl Default for CsvOptions {
+    /// CSV with the coordinates in columns named `x` and `y`.
+    fn default() -> Self {
+        Self::csv("x", "y")
+    }
+}
+
+/// What is wrong with the row of a [`CsvError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvErrorKind {
+    /// The header has no column of this name, or there is no header to look it up in.
+    UnknownColumn(String),
+    /// The row has only `len` fields, so the coordinate column at `index` is missing.
+    MissingField {
+        /// The position of the coordinate column.
+        index: usize,
+        /// The number of fields in the row.
+        len: usize,
+    },
+    /// A coordinate is not a valid value of the coordinate type.
+    InvalidCoordinate {
+        /// The position of the column.
+        column: usize,
+        /// The field as written.
+        value: String,
+    },
+    /// A quoted field is not closed before the end of the line.
+    UnterminatedQuote,
+}
+
+/// Why a table could not be read, and on which line, counted from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvError {
+    /// The line of the offending row.
+    pub line: usize,
+    /// What is wrong with it.
+    pub kind: CsvErrorKind,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            CsvErrorKind::UnknownColumn(name) => write!(f, "no column named {:?}", name),
+            CsvErrorKind::MissingField { index, len } => {
+                write!(f, "row has {} fields, so column {} is missing", len, index)
+            }
+            CsvErrorKind::InvalidCoordinate { column, value } => write!(
+                f,
+                "{:?} in column {} is not a valid coordinate",
+                value, column
+            ),
+            CsvErrorKind::UnterminatedQuote => write!(f, "unterminated quoted field"),
+        }
+    }
+}
+
+impl Error for CsvError {}
+
+/// A point together with the row it was read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record<T> {
+    /// The coordinates from the selected columns.
+    pub point: Point<T>,
+    /// Every field of the row, coordinates included, unquoted.
+    pub fields: Vec<String>,
+    /// The row as written in the input.
+    pub row: String,
+}
+
+/// The rows of a table, read as points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table<T> {
+    /// The column names, if the table has a header.
+    pub header: Option<Vec<String>>,
+    /// One record per data row, in file order.
+    pub records: Vec<Record<T>>,
+    header_row: Option<String>,
+    x: usize,
+    y: usize,
+}
+
+impl<T: Clone> Table<T> {
+    /// The points of all records, in file order, so that indices into the result are
+    /// indices into `records`.
+    pub fn points(&self) -> Vec<Point<T>> {
+        self.records
+            .iter()
+            .map(|record| record.point.clone())
+            .collect()
+    }
+
+    /// The fields of the record at `index` other than its coordinates, each paired with
+    /// its column name, or with its position if there is no header.
+    pub fn metadata(&self, index: usize) -> Vec<(String, &str)> {
+        self.records[index]
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|&(column, _)| column != self.x && column != self.y)
+            .map(|(column, value)| {
+                let name = match &self.header {
+                    Some(header) if column < header.len() => header[column].clone(),
+                    _ => column.to_string(),
+                };
+                (name, value.as_str())
+            })
+            .collect()
+    }
+
+    /// Writes the header row, if any, and the rows of the records at `indices` exactly as
+    /// they were read, quoting included. Passing the indices of a
+    /// [`CollinearPoints`](crate::collinear::CollinearPoints) exports a line's members.
+    pub fn to_csv(&self, indices: &[usize]) -> String {
+        let mut out = String::new();
+        let rows = self
+            .header_row
+            .iter()
+            .chain(indices.iter().map(|&index| &self.records[index].row));
+        for row in rows {
+            out.push_str(row);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Splits a line into fields. A field wrapped in double quotes may contain the delimiter,
+/// and `""` inside it stands for one quote.
+pub(crate) fn split_row(line: &str, delimiter: char) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next()? {
+                    '"' if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    '"' => break,
+                    c => field.push(c),
+                }
+            }
+        }
+        while let Some(c) = chars.next_if(|&c| c != delimiter) {
+            field.push(c);
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Some(fields);
+        }
+    }
+}
+
+fn column_index(
+    column: &Column,
+    header: Option<&[String]>,
+    line: usize,
+) -> Result<usize, CsvError> {
+    match column {
+        Column::Index(index) => Ok(*index),
+        Column::Name(name) => header
+            .and_then(|header| header.iter().position(|field| field.trim() == name))
+            .ok_or(CsvError {
+                line,
+                kind: CsvErrorKind::UnknownColumn(name.clone()),
+            }),
+    }
+}
+
+/// Reads the table in `text`. Blank lines are skipped, fields are trimmed before being
+/// parsed as coordinates, and the first bad row is reported by its line.
+pub fn read_csv<T: Coordinate>(text: &str, options: &CsvOptions) -> Result<Table<T>, CsvError> {
+    let mut rows = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            split_row(line, options.delimiter)
+                .map(|fields| (index + 1, line, fields))
+                .ok_or(CsvError {
+                    line: index + 1,
+                    kind: CsvErrorKind::UnterminatedQuote,
+                })
+        });
+    let header = if options.header {
+        rows.next().transpose()?
+    } else {
+        None
+    };
+    let header_line = header.as_ref().map_or(1, |(line, _, _)| *line);
+    let header_row = header.as_ref().map(|(_, row, _)| row.to_string());
+    let header = header.map(|(_, _, fields)| fields);
+    let x = column_index(&options.x, header.as_deref(), header_line)?;
+    let y = column_index(&options.y, header.as_deref(), header_line)?;
+    let records = rows
+        .map(|row| {
+            let (line, row, fields) = row?;
+            let coordinate = |index: usize| {
+                let value = fields.get(index).ok_or(CsvError {
+                    line,
+                    kind: CsvErrorKind::MissingField {
+                        index,
+                        len: fields.len(),
+                    },
+                })?;
+                value.trim().parse().map_err(|_| CsvError {
+                    line,
+                    kind: CsvErrorKind::InvalidCoordinate {
+                        column: index,
+                        value: value.clone(),
+                    },
+                })
+            };
+            Ok(Record {
+                point: Point {
+                    x: coordinate(x)?,
+                    y: coordinate(y)?,
+                },
+                fields,
+                row: row.to_string(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Table {
+        header,
+        records,
+        header_row,
+        x,
+        y,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collinear::{max_collinear_line, Duplicates};
+
+    const CLOUD: &str = "id,label,x,y,timestamp
+1,a,1,1,2024-01-01
+2,\"b, c\",3,2,2024-01-02
+
+3,d,5,3,2024-01-03
+4,\"say \"\"hi\"\"\",4,1,2024-01-04
+5,e,2,3,2024-01-05
+";
+
+    #[test]
+    fn test_read_csv() {
+        let table = read_csv::<i32>(CLOUD, &CsvOptions::default()).unwrap();
+        assert_eq!(
+            table.points(),
+            [(1, 1), (3, 2), (5, 3), (4, 1), (2, 3)].map(|(x, y)| Point { x, y })
+        );
+        assert_eq!(table.records[1].fields[1], "b, c");
+        assert_eq!(
+            table.metadata(3),
+            vec![
+                ("id".to_string(), "4"),
+                ("label".to_string(), "say \"hi\""),
+                ("timestamp".to_string(), "2024-01-04")
+            ]
+        );
+        let by_index = read_csv::<i32>(CLOUD, &CsvOptions::csv(2, 3)).unwrap();
+        assert_eq!(by_index, table);
+    }
+
+    #[test]
+    fn test_read_tsv_without_header() {
+        let options = CsvOptions {
+            header: false,
+            ..CsvOptions::tsv(1, 0)
+        };
+        let table = read_csv::<i64>("2\t1\tfirst\n 4 \t3\tsecond\n", &options).unwrap();
+        assert_eq!(table.header, None);
+        assert_eq!(
+            table.points(),
+            [(1, 2), (3, 4)].map(|(x, y)| Point { x, y })
+        );
+        assert_eq!(table.metadata(1), vec![("2".to_string(), "second")]);
+        assert_eq!(table.to_csv(&[1]), " 4 \t3\tsecond\n");
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        let read = |text: &str, options: &CsvOptions| read_csv::<i8>(text, options).unwrap_err();
+        let options = CsvOptions::default();
+        assert_eq!(
+            read("\nid,x,z\n1,2,3\n", &options),
+            CsvError {
+                line: 2,
+                kind: CsvErrorKind::UnknownColumn("y".to_string())
+            }
+        );
+        assert_eq!(
+            read("x,y\n1,2\n3\n", &options),
+            CsvError {
+                line: 3,
+                kind: CsvErrorKind::MissingField { index: 1, len: 1 }
+            }
+        );
+        assert_eq!(
+            read("x,y\n1,2\n3,300\n", &options),
+            CsvError {
+                line: 3,
+                kind: CsvErrorKind::InvalidCoordinate {
+                    column: 1,
+                    value: "300".to_string()
+                }
+            }
+        );
+        assert_eq!(
+            read("x,y,label\n1,2,\"open\n", &options),
+            CsvError {
+                line: 2,
+                kind: CsvErrorKind::UnterminatedQuote
+            }
+        );
+        let headless = CsvOptions {
+            header: false,
+            ..CsvOptions::default()
+        };
+        assert_eq!(
+            read("1,2\n", &headless).to_string(),
+            "line 1: no column named \"x\""
+        );
+    }
+
+    #[test]
+    fn test_export_best_line() {
+        let table = read_csv::<i32>(CLOUD, &CsvOptions::default()).unwrap();
+        let best = max_collinear_line(&table.points(), Duplicates::Count).unwrap();
+        assert_eq!(
+            table.to_csv(&best.indices),
+            "id,label,x,y,timestamp
+1,a,1,1,2024-01-01
+2,\"b, c\",3,2,2024-01-02
+3,d,5,3,2024-01-03
+"
+        );
+        let exported = table.to_csv(&[0, 1, 2, 3, 4]);
+        let reread = read_csv::<i32>(&exported, &CsvOptions::default()).unwrap();
+        assert_eq!(reread.records, table.records);
+        // Quoting that was not needed survives, as do spaces around fields.
+        let text = "id,label,x,y\n1,\"a\",1,1\n2, b ,2,\"2\"\n";
+        let table = read_csv::<i32>(text, &CsvOptions::default()).unwrap();
+        assert_eq!(table.to_csv(&[0, 1]), text);
+    }
+}
@@ -0,0 +1,293 @@
+//! Point sets with planted structure and a known answer, for benchmarks and tests that
+//! need inputs larger than anything checked by hand.
+
+use crate::geometry::Point;
+use crate::json::SCHEMA_VERSION;
+use crate::leetcode::to_leetcode;
+use crate::random::SplitMix64;
+
+/// A family of point sets whose maximum number of collinear points is known by
+/// construction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Workload {
+    /// `on_line` points of a hidden line among `noise` points on a parabola, none of them
+    /// on the line. Needs `on_line >= 3`, since the noise alone can put three points on a
+    /// line with one of the line's points.
+    PlantedLine {
+        /// The number of points on the hidden line.
+        on_line: usize,
+        /// The number of other points.
+        noise: usize,
+    },
+    /// Every lattice point of a `width × height` rectangle.
+    Grid {
+        /// The number of columns.
+        width: usize,
+        /// The number of rows.
+        height: usize,
+    },
+    /// `count` lattice points, all on one line with a random direction.
+    LatticeLine {
+        /// The number of points.
+        count: usize,
+    },
+    /// `count` points on the parabola `y = x²`, no three of them collinear.
+    Parabola {
+        /// The number of points.
+        count: usize,
+    },
+    /// `clusters` distinct points on a parabola, each repeated between 1 and `copies`
+    /// times.
+    ClusteredDuplicates {
+        /// The number of distinct points.
+        clusters: usize,
+        /// The largest number of copies of one point.
+        copies: usize,
+    },
+}
+
+/// A generated point set and its answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instance {
+    /// The points, in random order.
+    pub points: Vec<Point<i64>>,
+    /// The maximum number of points on one line, counting duplicates.
+    pub max: usize,
+}
+
+impl Instance {
+    /// Writes the instance as a JSON point list with its answer in an extra `max` field,
+    /// which [`points_from_json`](crate::json::points_from_json) ignores.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"version\":{},\"max\":{},\"points\":{}}}",
+            SCHEMA_VERSION,
+            self.max,
+            to_leetcode(&self.points)
+        )
+    }
+}
+
+fn shuffle<V>(rng: &mut SplitMix64, items: &mut [V]) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.below(i as u64 + 1) as usize);
+    }
+}
+
+/// `count` points `(t, t²)` for consecutive `t` starting at a random offset around zero.
+fn parabola(rng: &mut SplitMix64, count: usize) -> impl Iterator<Item = Point<i64>> {
+    let start = rng.between(-(count as i64), 0);
+    (start..).take(count).map(|t| Point { x: t, y: t * t })
+}
+
+/// Generates an instance of `workload`, entirely determined by the state of `rng`.
+///
+/// # Panics
+///
+/// Panics for a [`Workload::PlantedLine`] with fewer than three points on the line.
+pub fn generate(workload: Workload, rng: &mut SplitMix64) -> Instance {
+    let (mut points, max): (Vec<Point<i64>>, usize) = match workload {
+        Workload::PlantedLine { on_line, noise } => {
+            assert!(on_line >= 3, "a planted line needs at least three points");
+            let origin = (rng.between(-1000, 1000), rng.between(-1000, 1000));
+            let direction = match (rng.between(0, 5), rng.between(-5, 5)) {
+                (0, _) => (0, 1),
+                direction => direction,
+            };
+            let line = (0..on_line as i64).map(|k| Point {
+                x: origin.0 + k * direction.0,
+                y: origin.1 + k * direction.1,
+            });
+            // The parabola meets the line at most twice; those points are left out.
+            let on_the_line = |point: &Point<i64>| {
+                (point.x - origin.0) * direction.1 == (point.y - origin.1) * direction.0
+            };
+            let noise = parabola(rng, noise + 2)
+                .filter(|point| !on_the_line(point))
+                .take(noise);
+            (line.chain(noise).collect(), on_line)
+        }
+        Workload::Grid { width, height } => {
+            let corner = (rng.between(-1000, 1000), rng.between(-1000, 1000));
+            let points = (0..width as i64)
+                .flat_map(|i| {
+                    (0..height as i64).map(move |j| Point {
+                        x: corner.0 + i,
+                        y: corner.1 + j,
+                    })
+                })
+                .collect();
+            let max = if width == 0 || height == 0 {
+                0
+            } else {
+                width.max(height)
+            };
+            (points, max)
+        }
+        Workload::LatticeLine { count } => {
+            let origin = (rng.between(-1000, 1000), rng.between(-1000, 1000));
+            let direction = match (rng.between(-100, 100), rng.between(-100, 100)) {
+                (0, 0) => (1, 1),
+                direction => direction,
+            };
+            let points = (0..count as i64)
+                .map(|k| Point {
+                    x: origin.0 + k * direction.0,
+                    y: origin.1 + k * direction.1,
+                })
+                .collect();
+            (points, count)
+        }
+        Workload::Parabola { count } => (parabola(rng, count).collect(), count.min(2)),
+        Workload::ClusteredDuplicates { clusters, copies } => {
+            let mut multiplicities: Vec<usize> = (0..clusters)
+                .map(|_| rng.between(1, copies.max(1) as i64) as usize)
+                .collect();
+            let points = parabola(rng, clusters)
+                .zip(&multiplicities)
+                .flat_map(|(point, &multiplicity)| std::iter::repeat_n(point, multiplicity))
+                .collect();
+            // No three clusters are collinear, so the best line joins the two largest.
+            multiplicities.sort_unstable_by(|a, b| b.cmp(a));
+            (points, multiplicities.iter().take(2).sum())
+        }
+    };
+    shuffle(rng, &mut points);
+    Instance { points, max }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collinear::{max_collinear_line, Duplicates};
+    use crate::json::points_from_json;
+    use crate::oracle::brute_force_max_points;
+
+    fn check(workload: Workload, seed: u64) -> Instance {
+        let instance = generate(workload, &mut SplitMix64::new(seed));
+        let best = max_collinear_line(&instance.points, Duplicates::Count);
+        assert_eq!(
+            best.map_or(0, |best| best.count()),
+            instance.max,
+            "{:?}",
+            workload
+        );
+        instance
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let planted = check(
+                Workload::PlantedLine {
+                    on_line: 3 + seed as usize,
+                    noise: 40,
+                },
+                seed,
+            );
+            assert_eq!(planted.points.len(), 43 + seed as usize);
+            check(
+                Workload::Grid {
+                    width: 7,
+                    height: 4,
+                },
+                seed,
+            );
+            check(
+                Workload::Grid {
+                    width: 3,
+                    height: 9,
+                },
+                seed,
+            );
+            check(Workload::LatticeLine { count: 25 }, seed);
+            check(Workload::Parabola { count: 30 }, seed);
+            let clusters = check(
+                Workload::ClusteredDuplicates {
+                    clusters: 12,
+                    copies: 5,
+                },
+                seed,
+            );
+            assert!(clusters.points.len() >= 12);
+        }
+        assert_eq!(
+            check(
+                Workload::Grid {
+                    width: 0,
+                    height: 5
+                },
+                1
+            )
+            .max,
+            0
+        );
+        assert_eq!(check(Workload::Parabola { count: 1 }, 1).max, 1);
+        let cluster = check(
+            Workload::ClusteredDuplicates {
+                clusters: 1,
+                copies: 1,
+            },
+            1,
+        );
+        assert_eq!(cluster.max, 1);
+    }
+
+    #[test]
+    fn test_generate_agrees_with_brute_force() {
+        let workloads = [
+            Workload::PlantedLine {
+                on_line: 4,
+                noise: 12,
+            },
+            Workload::Grid {
+                width: 4,
+                height: 3,
+            },
+            Workload::LatticeLine { count: 10 },
+            Workload::Parabola { count: 15 },
+            Workload::ClusteredDuplicates {
+                clusters: 6,
+                copies: 4,
+            },
+        ];
+        for seed in 0..10 {
+            for workload in workloads {
+                let instance = generate(workload, &mut SplitMix64::new(seed));
+                let points: Vec<Point<i32>> = instance
+                    .points
+                    .iter()
+                    .map(|point| Point {
+                        x: point.x as i32,
+                        y: point.y as i32,
+                    })
+                    .collect();
+                assert_eq!(brute_force_max_points(&points), instance.max);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_is_seeded() {
+        let workload = Workload::PlantedLine {
+            on_line: 5,
+            noise: 5,
+        };
+        let instance = generate(workload, &mut SplitMix64::new(4));
+        assert_eq!(instance, generate(workload, &mut SplitMix64::new(4)));
+        assert_ne!(instance, generate(workload, &mut SplitMix64::new(5)));
+        assert_eq!(points_from_json(&instance.to_json()), Ok(instance.points));
+    }
+
+    #[test]
+    #[should_panic(expected = "a planted line needs at least three points")]
+    fn test_planted_line_needs_three_points() {
+        generate(
+            Workload::PlantedLine {
+                on_line: 2,
+                noise: 2,
+            },
+            &mut SplitMix64::new(0),
+        );
+    }
+}
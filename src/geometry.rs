@@ -0,0 +1,595 @@
+//! Points, slopes and lines with integer coordinates.
+
+use crate::rational::{BigInt, Integer, ParseRationalError, RationalNumber};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// An integer type usable as a point coordinate. Geometry is carried out in the wider
+/// `Wide` type, which holds coordinate differences and cross products exactly.
+pub trait Coordinate: Clone + Debug + Display + FromStr + Eq + Ord + Hash {
+    /// An integer type holding coordinate differences and their products exactly.
+    type Wide: Integer;
+
+    /// The coordinate as a value of the wide type.
+    fn widen(&self) -> Self::Wide;
+}
+
+macro_rules! impl_coordinate {
+    ($($coordinate:ty => $wide:ty),*) => {
+        $(
+            impl Coordinate for $coordinate {
+                type Wide = $wide;
+
+                fn widen(&self) -> $wide {
+                    <$wide>::from(*self)
+                }
+            }
+        )*
+    };
+}
+
+// Cross products of `u64` and 128-bit coordinates do not fit in any primitive.
+impl_coordinate!(
+    i8 => i64,
+    i16 => i64,
+    i32 => i64,
+    i64 => i128,
+    i128 => BigInt,
+    u8 => i64,
+    u16 => i64,
+    u32 => i128,
+    u64 => BigInt,
+    u128 => BigInt
+);
+
+impl Coordinate for BigInt {
+    type Wide = BigInt;
+
+    fn widen(&self) -> BigInt {
+        self.clone()
+    }
+}
+
+/// A point in the plane.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Point<T> {
+    /// The horizontal coordinate.
+    pub x: T,
+    /// The vertical coordinate.
+    pub y: T,
+}
+
+/// The slope of the line through two points.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Slope<T: Coordinate> {
+    /// The line is vertical.
+    Undefined,
+    /// Rise over run, exactly.
+    Defined(RationalNumber<T::Wide>),
+}
+
+fn exact<V>(value: Option<V>) -> V {
+    value.expect("coordinates too large for exact arithmetic in their wide type")
+}
+
+/// The slope of the line through `a` and `b`, which is undefined if they share an x
+/// coordinate.
+pub fn slope<T: Coordinate>(a: &Point<T>, b: &Point<T>) -> Slope<T> {
+    if a.x == b.x {
+        Slope::Undefined
+    } else {
+        Slope::Defined(RationalNumber::new(
+            exact(a.y.widen().checked_sub(&b.y.widen())),
+            exact(a.x.widen().checked_sub(&b.x.widen())),
+        ))
+    }
+}
+
+/// The line `a·x + b·y = c` in a normalized form: `gcd(a, b, c) = 1`, and either `b > 0`,
+/// or `b = 0` and `a > 0`. Each line has exactly one such form, so any two distinct
+/// points of a line produce the same key. A non-vertical line through two lattice points,
+/// with slope `n/d` in lowest terms, has `a = -n` and `b = d`, but a fractional intercept
+/// can scale both up: `y = 1/2` is `0·x + 2·y = 1`.
+///
+/// The coefficients are carried in `T::Wide`, which holds them exactly for any pair of
+/// points. Lines are ordered lexicographically by `(a, b, c)`, which is the canonical
+/// order used to break ties between equally rich lines.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Line<T: Coordinate> {
+    a: T::Wide,
+    b: T::Wide,
+    c: T::Wide,
+}
+
+impl<T: Coordinate> Line<T> {
+    /// The line through two distinct points.
+    pub fn new(a: &Point<T>, b: &Point<T>) -> Self {
+        match slope(a, b) {
+            Slope::Undefined => Line {
+                a: T::Wide::one(),
+                b: T::Wide::zero(),
+                c: a.x.widen(),
+            },
+            Slope::Defined(slope) => {
+                // `c = d·a.y - n·a.x` can overflow in its intermediate products, so it is
+                // derived from the cross product of the two points instead, which always
+                // fits: `(a.x - b.x) / d` is the factor by which the slope was reduced.
+                let cross = exact(
+                    exact(a.x.widen().checked_mul(&b.y.widen()))
+                        .checked_sub(&exact(b.x.widen().checked_mul(&a.y.widen()))),
+                );
+                let scale =
+                    exact(a.x.widen().checked_sub(&b.x.widen())) / slope.denominator().clone();
+                Line {
+                    a: exact(slope.numerator().checked_neg()),
+                    b: slope.denominator().clone(),
+                    c: cross / scale,
+                }
+            }
+        }
+    }
+
+    /// The horizontal line through `point`, used where a single point leaves the line
+    /// undetermined.
+    pub fn horizontal_through(point: &Point<T>) -> Self {
+        Line {
+            a: T::Wide::zero(),
+            b: T::Wide::one(),
+            c: point.y.widen(),
+        }
+    }
+
+    /// The vertical line `x = constant`.
+    pub fn vertical(constant: RationalNumber<T::Wide>) -> Self {
+        Line {
+            a: constant.denominator().clone(),
+            b: T::Wide::zero(),
+            c: constant.numerator().clone(),
+        }
+    }
+
+    /// The line `y = slope·x + intercept`, or `None` if its coefficients overflow the wide
+    /// type.
+    pub fn from_slope_intercept(
+        slope: &RationalNumber<T::Wide>,
+        intercept: &RationalNumber<T::Wide>,
+    ) -> Option<Self> {
+        // Scaling `y = n/d·x + p/q` by `lcm(d, q)` leaves coprime integer coefficients, and
+        // the reduced ratio `d/q` supplies both `lcm(d, q) / d` and `lcm(d, q) / q`.
+        let ratio =
+            RationalNumber::new(slope.denominator().clone(), intercept.denominator().clone());
+        Some(Line {
+            a: slope
+                .numerator()
+                .checked_mul(ratio.denominator())?
+                .checked_neg()?,
+            b: slope.denominator().checked_mul(ratio.denominator())?,
+            c: intercept.numerator().checked_mul(ratio.numerator())?,
+        })
+    }
+
+    /// The line `a·x + b·y = c` in its normalized form, or `None` if `a` and `b` are both
+    /// zero or the normalized coefficients overflow the wide type.
+    pub fn from_coefficients(a: T::Wide, b: T::Wide, c: T::Wide) -> Option<Self> {
+        let integer = |value| RationalNumber::new(value, T::Wide::one());
+        let (a, b, c) = (integer(a), integer(b), integer(c));
+        if b.is_zero() {
+            Some(Line::vertical(c.checked_div(&a)?))
+        } else {
+            Line::from_slope_intercept(&a.checked_neg()?.checked_div(&b)?, &c.checked_div(&b)?)
+        }
+    }
+
+    /// The coefficient of x.
+    pub fn a(&self) -> &T::Wide {
+        &self.a
+    }
+
+    /// The coefficient of y.
+    pub fn b(&self) -> &T::Wide {
+        &self.b
+    }
+
+    /// The constant on the right-hand side.
+    pub fn c(&self) -> &T::Wide {
+        &self.c
+    }
+
+    /// The slope of the line.
+    pub fn slope(&self) -> Slope<T> {
+        if self.b == T::Wide::zero() {
+            Slope::Undefined
+        } else {
+            Slope::Defined(RationalNumber::new(-self.a.clone(), self.b.clone()))
+        }
+    }
+
+    /// Whether `point` lies on the line.
+    pub fn contains(&self, point: &Point<T>) -> bool {
+        // For lines through two points of `T` the products always fit in `T::Wide`, and a
+        // sum that overflows cannot equal `c`.
+        let sum = self
+            .a
+            .checked_mul(&point.x.widen())
+            .zip(self.b.checked_mul(&point.y.widen()))
+            .and_then(|(ax, by)| ax.checked_add(&by));
+        sum.as_ref() == Some(&self.c)
+    }
+}
+
+impl<T: Coordinate> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Why a string is not a point of the form `(x, y)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParsePointError {
+    /// The text does not start with `(`.
+    MissingOpeningParenthesis,
+    /// The text does not end with `)`.
+    MissingClosingParenthesis,
+    /// There is no `,` between the coordinates.
+    MissingComma,
+    /// The x coordinate is not a valid value of the coordinate type.
+    InvalidX(String),
+    /// The y coordinate is not a valid value of the coordinate type.
+    InvalidY(String),
+}
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePointError::MissingOpeningParenthesis => write!(f, "expected '(' before point"),
+            ParsePointError::MissingClosingParenthesis => write!(f, "expected ')' after point"),
+            ParsePointError::MissingComma => write!(f, "expected ',' between coordinates"),
+            ParsePointError::InvalidX(text) => write!(f, "invalid x coordinate {:?}", text),
+            ParsePointError::InvalidY(text) => write!(f, "invalid y coordinate {:?}", text),
+        }
+    }
+}
+
+impl Error for ParsePointError {}
+
+impl<T: Coordinate> FromStr for Point<T> {
+    type Err = ParsePointError;
+
+    /// Parses `(x, y)`, allowing whitespace around each part.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('(')
+            .ok_or(ParsePointError::MissingOpeningParenthesis)?
+            .strip_suffix(')')
+            .ok_or(ParsePointError::MissingClosingParenthesis)?;
+        let (x, y) = inner.split_once(',').ok_or(ParsePointError::MissingComma)?;
+        let (x, y) = (x.trim(), y.trim());
+        Ok(Point {
+            x: x.parse()
+                .map_err(|_| ParsePointError::InvalidX(x.to_string()))?,
+            y: y.parse()
+                .map_err(|_| ParsePointError::InvalidY(y.to_string()))?,
+        })
+    }
+}
+
+impl<T: Coordinate> fmt::Display for Slope<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Slope::Undefined => write!(f, "undefined"),
+            Slope::Defined(slope) => write!(f, "{}", slope),
+        }
+    }
+}
+
+impl<T: Coordinate> FromStr for Slope<T> {
+    type Err = ParseRationalError;
+
+    /// Parses `undefined` or a rational slope.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == "undefined" {
+            Ok(Slope::Undefined)
+        } else {
+            s.parse().map(Slope::Defined)
+        }
+    }
+}
+
+impl<T: Coordinate> fmt::Display for Line<T> {
+    /// Formats as `x = c` for vertical lines and as `y = m·x + q` otherwise, omitting
+    /// zero terms.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.b == T::Wide::zero() {
+            return write!(
+                f,
+                "x = {}",
+                RationalNumber::new(self.c.clone(), self.a.clone())
+            );
+        }
+        let slope = RationalNumber::new(-self.a.clone(), self.b.clone());
+        let intercept = RationalNumber::new(self.c.clone(), self.b.clone());
+        if slope.is_zero() {
+            return write!(f, "y = {}", intercept);
+        }
+        write!(f, "y = {}·x", slope)?;
+        match intercept.cmp(&RationalNumber::zero()) {
+            // Negating the intercept could overflow, so only its sign is dropped.
+            Ordering::Less => write!(f, " - {}", &intercept.to_string()[1..]),
+            Ordering::Equal => Ok(()),
+            Ordering::Greater => write!(f, " + {}", intercept),
+        }
+    }
+}
+
+/// Why a string is not a line in the form produced by `Display`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseLineError {
+    /// The text does not start with `x =` or `y =`.
+    MissingLeftHandSide,
+    /// The slope is not a valid rational.
+    InvalidSlope(ParseRationalError),
+    /// The slope is not followed by `·x`.
+    MissingX,
+    /// The intercept (or, for `x = c`, the constant) is not a valid rational.
+    InvalidIntercept(ParseRationalError),
+    /// Something other than `+` or `-` joins the `x` term and the intercept.
+    InvalidOperator(String),
+    /// The line's coefficients are not representable in the wide coordinate type.
+    Overflow,
+}
+
+impl fmt::Display for ParseLineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseLineError::MissingLeftHandSide => write!(f, "expected 'x =' or 'y ='"),
+            ParseLineError::InvalidSlope(error) => write!(f, "invalid slope: {}", error),
+            ParseLineError::MissingX => write!(f, "expected 'x' after the slope"),
+            ParseLineError::InvalidIntercept(error) => write!(f, "invalid intercept: {}", error),
+            ParseLineError::InvalidOperator(text) => {
+                write!(f, "expected '+' or '-' before intercept, found {:?}", text)
+            }
+            ParseLineError::Overflow => write!(f, "line coefficients are out of range"),
+        }
+    }
+}
+
+impl Error for ParseLineError {}
+
+impl<T: Coordinate> FromStr for Line<T> {
+    type Err = ParseLineError;
+
+    /// Parses the forms produced by `Display`: `x = c`, `y = q`, `y = m·x` and
+    /// `y = m·x ± q`, where `*` may stand in for `·`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let equation = |variable| {
+            s.strip_prefix(variable)
+                .and_then(|rest: &str| rest.trim_start().strip_prefix('='))
+        };
+        if let Some(constant) = equation('x') {
+            let constant = constant.parse().map_err(ParseLineError::InvalidIntercept)?;
+            return Ok(Line::vertical(constant));
+        }
+        let right_hand_side = equation('y').ok_or(ParseLineError::MissingLeftHandSide)?;
+        let (slope, intercept) = match right_hand_side.split_once(['·', '*']) {
+            None => (
+                RationalNumber::zero(),
+                right_hand_side
+                    .parse()
+                    .map_err(ParseLineError::InvalidIntercept)?,
+            ),
+            Some((slope, rest)) => {
+                let slope = slope.parse().map_err(ParseLineError::InvalidSlope)?;
+                let rest = rest
+                    .trim_start()
+                    .strip_prefix('x')
+                    .ok_or(ParseLineError::MissingX)?
+                    .trim();
+                let intercept = if rest.is_empty() {
+                    RationalNumber::zero()
+                } else if let Some(intercept) = rest.strip_prefix('+') {
+                    intercept
+                        .parse()
+                        .map_err(ParseLineError::InvalidIntercept)?
+                } else if let Some(intercept) = rest.strip_prefix('-') {
+                    // Parsed with its sign, the magnitude of the least intercept fits.
+                    format!("-{}", intercept.trim_start())
+                        .parse()
+                        .map_err(ParseLineError::InvalidIntercept)?
+                } else {
+                    return Err(ParseLineError::InvalidOperator(rest.to_string()));
+                };
+                (slope, intercept)
+            }
+        };
+        Line::from_slope_intercept(&slope, &intercept).ok_or(ParseLineError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SplitMix64;
+    use crate::test_support::{cross, distinct_random_points, random_points};
+
+    #[test]
+    fn test_from_coefficients() {
+        let line = |a: i64, b: i64, c: i64| Line::<i32>::from_coefficients(a, b, c);
+        let through =
+            |(x1, y1), (x2, y2)| Line::new(&Point { x: x1, y: y1 }, &Point { x: x2, y: y2 });
+        assert_eq!(line(4, 2, 6), Some(through((0, 3), (1, 1))));
+        assert_eq!(line(-3, 0, -15), Some(through((5, 0), (5, 7))));
+        assert_eq!(line(0, -7, 14), Some(through((0, -2), (9, -2))));
+        assert_eq!(
+            line(3, 0, 1).map(|line| line.to_string()),
+            Some("x = 1/3".to_string())
+        );
+        assert_eq!(line(0, 0, 1), None);
+        assert_eq!(line(1, i64::MIN, 0), None);
+        let mut rng = SplitMix64::new(11);
+        for pair in distinct_random_points(&mut rng, 200, 1000).chunks_exact(2) {
+            let expected = Line::new(&pair[0], &pair[1]);
+            let (a, b, c) = (*expected.a(), *expected.b(), *expected.c());
+            assert_eq!(line(3 * a, 3 * b, 3 * c), Some(expected));
+            assert_eq!(line(-a, -b, -c), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let point = Point { x: 1, y: -2 };
+        assert_eq!(point.to_string(), "(1, -2)");
+        assert_eq!(Slope::<i32>::Undefined.to_string(), "undefined");
+        assert_eq!(
+            slope(&Point { x: 0, y: 0 }, &Point { x: 4, y: 3 }).to_string(),
+            "3/4"
+        );
+        let line = |(x1, y1), (x2, y2)| Line::new(&Point { x: x1, y: y1 }, &Point { x: x2, y: y2 });
+        assert_eq!(line((5, 0), (5, 7)).to_string(), "x = 5");
+        assert_eq!(line((0, 3), (7, 3)).to_string(), "y = 3");
+        assert_eq!(line((1, 1), (2, 2)).to_string(), "y = 1·x");
+        assert_eq!(line((2, 2), (6, 5)).to_string(), "y = 3/4·x + 1/2");
+        assert_eq!(line((0, -1), (-2, 1)).to_string(), "y = -1·x - 1");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let mut rng = SplitMix64::new(0xa54f_f53a_5f1d_36f1);
+        for _ in 0..50 {
+            let points = distinct_random_points(&mut rng, 10, 20);
+            for p in &points {
+                assert_eq!(p.to_string().parse(), Ok(*p));
+                for q in points.iter().filter(|q| *q != p) {
+                    let line = Line::new(p, q);
+                    assert_eq!(line.to_string().parse(), Ok(line), "{}", line);
+                    let slope = slope(p, q);
+                    assert_eq!(slope.to_string().parse(), Ok(slope));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let parsed = |text: &str| text.parse::<Line<i32>>();
+        let line = |(x1, y1), (x2, y2)| Line::new(&Point { x: x1, y: y1 }, &Point { x: x2, y: y2 });
+        assert_eq!(parsed("y=3/4*x+1/2"), Ok(line((2, 2), (6, 5))));
+        assert_eq!(parsed(" x = -2 "), Ok(line((-2, 0), (-2, 9))));
+        assert_eq!(parsed("y = 0·x - 3"), Ok(line((0, -3), (1, -3))));
+        // Lines need not pass through any integer point.
+        assert_eq!(
+            parsed("y = 1/2").map(|line| line.to_string()),
+            Ok("y = 1/2".to_string())
+        );
+        assert_eq!(
+            parsed("x = 5/3").map(|line| line.to_string()),
+            Ok("x = 5/3".to_string())
+        );
+        let coefficients = |line: Line<i32>| (*line.a(), *line.b(), *line.c());
+        assert_eq!(parsed("y = 1/2").map(coefficients), Ok((0, 2, 1)));
+        assert_eq!(parsed("y = -2/3·x + 1/6").map(coefficients), Ok((4, 6, 1)));
+        assert_eq!(parsed("x = -5/3").map(coefficients), Ok((3, 0, -5)));
+        assert_eq!(parsed("z = 1"), Err(ParseLineError::MissingLeftHandSide));
+        assert_eq!(
+            parsed("y = 3/0·x"),
+            Err(ParseLineError::InvalidSlope(
+                ParseRationalError::ZeroDenominator
+            ))
+        );
+        assert_eq!(parsed("y = 2·z"), Err(ParseLineError::MissingX));
+        assert_eq!(
+            parsed("y = 2·x 3"),
+            Err(ParseLineError::InvalidOperator("3".to_string()))
+        );
+        assert_eq!(
+            parsed("y = 2·x + q"),
+            Err(ParseLineError::InvalidIntercept(
+                ParseRationalError::InvalidNumerator("q".to_string())
+            ))
+        );
+        assert_eq!(
+            "y = 9223372036854775807/2·x + 1/3".parse::<Line<i32>>(),
+            Err(ParseLineError::Overflow)
+        );
+        let least = parsed("y = 1·x + -9223372036854775808").unwrap();
+        assert_eq!(least.to_string(), "y = 1·x - 9223372036854775808");
+        assert_eq!(parsed(&least.to_string()), Ok(least));
+        let least = parsed("y = 1·x - 9223372036854775808/7").unwrap();
+        assert_eq!(least.to_string(), "y = 1·x - 9223372036854775808/7");
+    }
+
+    #[test]
+    fn test_parse_point_errors() {
+        let parsed = |text: &str| text.parse::<Point<u8>>();
+        assert_eq!(parsed(" ( 1 ,2 ) "), Ok(Point { x: 1, y: 2 }));
+        assert_eq!(
+            parsed("1, 2)"),
+            Err(ParsePointError::MissingOpeningParenthesis)
+        );
+        assert_eq!(
+            parsed("(1, 2"),
+            Err(ParsePointError::MissingClosingParenthesis)
+        );
+        assert_eq!(parsed("(1 2)"), Err(ParsePointError::MissingComma));
+        assert_eq!(
+            parsed("(-1, 2)"),
+            Err(ParsePointError::InvalidX("-1".to_string()))
+        );
+        assert_eq!(
+            parsed("(1, 2, 3)"),
+            Err(ParsePointError::InvalidY("2, 3".to_string()))
+        );
+    }
+
+    const EXTREMES: [i32; 6] = [i32::MIN, i32::MIN + 1, -1, 0, i32::MAX - 1, i32::MAX];
+
+    #[test]
+    fn test_line_at_extreme_coordinates() {
+        let points: Vec<Point<i32>> = EXTREMES
+            .iter()
+            .flat_map(|&x| EXTREMES.iter().map(move |&y| Point { x, y }))
+            .collect();
+        for p in &points {
+            for q in points.iter().filter(|q| *q != p) {
+                let line = Line::new(p, q);
+                assert_eq!(line, Line::new(q, p));
+                assert!(on_line(&line, p) && on_line(&line, q));
+                for r in points.iter().filter(|r| *r != p && *r != q) {
+                    assert_eq!(line == Line::new(p, r), cross(p, q, r) == 0);
+                }
+            }
+        }
+    }
+
+    fn on_line(line: &Line<i32>, point: &Point<i32>) -> bool {
+        let on_line = i128::from(line.a) * i128::from(point.x)
+            + i128::from(line.b) * i128::from(point.y)
+            == i128::from(line.c);
+        assert_eq!(line.contains(point), on_line);
+        on_line
+    }
+
+    #[test]
+    fn test_line_key_is_shared_by_all_points_on_the_line() {
+        let mut rng = SplitMix64::new(0x2545_f491_4f6c_dd1d);
+        for _ in 0..200 {
+            let points = random_points(&mut rng, 12, 6);
+            for p in &points {
+                for q in points.iter().filter(|q| *q != p) {
+                    let line = Line::new(p, q);
+                    assert_eq!(line, Line::new(q, p));
+                    assert!(on_line(&line, p) && on_line(&line, q));
+                    for r in points.iter().filter(|r| *r != p && *r != q) {
+                        let collinear = cross(p, q, r) == 0;
+                        assert_eq!(line == Line::new(p, r), collinear);
+                        assert_eq!(on_line(&line, r), collinear);
+                    }
+                }
+            }
+        }
+    }
+}
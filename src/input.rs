@@ -0,0 +1,219 @@
+//! Conversion of raw `[x, y]` rows into points, and the LeetCode entry point.
+
+use crate::collinear::{max_collinear_count, Duplicates};
+use crate::geometry::{Coordinate, Point};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Why a list of `[x, y]` rows could not be used as input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputError {
+    /// There are no points.
+    Empty,
+    /// The row at this index does not have exactly two values.
+    WrongArity {
+        /// The index of the row.
+        row: usize,
+        /// The number of values in the row.
+        len: usize,
+    },
+    /// A value does not fit in the coordinate type.
+    OutOfRange {
+        /// The index of the row.
+        row: usize,
+        /// The index of the value within its row.
+        column: usize,
+        /// The value as written.
+        value: String,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "no points given"),
+            InputError::WrongArity { row, len } => {
+                write!(f, "row {} has {} values, expected 2", row, len)
+            }
+            InputError::OutOfRange { row, column, value } => write!(
+                f,
+                "value {} in row {}, column {} is out of range for the coordinate type",
+                value, row, column
+            ),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Converts `[x, y]` rows into points with coordinates of type `T`.
+pub fn points_from_rows<T, S>(rows: &[Vec<S>]) -> Result<Vec<Point<T>>, InputError>
+where
+    T: Coordinate + TryFrom<S>,
+    S: Clone + Display,
+{
+    let coordinate = |row: usize, column: usize, value: &S| {
+        T::try_from(value.clone()).map_err(|_| InputError::OutOfRange {
+            row,
+            column,
+            value: value.to_string(),
+        })
+    };
+    rows.iter()
+        .enumerate()
+        .map(|(row, values)| match values.as_slice() {
+            [x, y] => Ok(Point {
+                x: coordinate(row, 0, x)?,
+                y: coordinate(row, 1, y)?,
+            }),
+            _ => Err(InputError::WrongArity {
+                row,
+                len: values.len(),
+            }),
+        })
+        .collect()
+}
+
+/// The maximum number of points on one line, for `[x, y]` rows converted to coordinates
+/// of type `T`. Duplicate points are counted.
+pub fn try_max_collinear_points<T, S>(raw_points: &[Vec<S>]) -> Result<usize, InputError>
+where
+    T: Coordinate + TryFrom<S>,
+    S: Clone + Display,
+{
+    let points = points_from_rows::<T, S>(raw_points)?;
+    match max_collinear_count(&points, Duplicates::Count) {
+        0 => Err(InputError::Empty),
+        count => Ok(count),
+    }
+}
+
+/// The LeetCode entry point. An empty input has no points on any line; rows that are not
+/// `[x, y]` pairs violate the problem's constraints and panic.
+pub fn max_collinear_points(raw_points: Vec<Vec<i32>>) -> i32 {
+    match try_max_collinear_points::<i32, i32>(&raw_points) {
+        Ok(count) => count as i32,
+        Err(InputError::Empty) => 0,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rational::BigInt;
+
+    #[test]
+    fn test_0() {
+        let max_points = max_collinear_points(vec![vec![0, 0]]);
+        assert_eq!(max_points, 1);
+    }
+
+    #[test]
+    fn test_1() {
+        let max_points = max_collinear_points(vec![vec![1, 1], vec![2, 2], vec![3, 3]]);
+        assert_eq!(max_points, 3);
+    }
+
+    #[test]
+    fn test_2() {
+        let max_points = max_collinear_points(vec![
+            vec![1, 1],
+            vec![3, 2],
+            vec![5, 3],
+            vec![4, 1],
+            vec![2, 3],
+            vec![1, 4],
+        ]);
+        assert_eq!(max_points, 4);
+    }
+
+    #[test]
+    fn test_parallel_lines_are_distinct() {
+        let max_points = max_collinear_points(vec![
+            vec![0, 0],
+            vec![2, 1],
+            vec![0, 1],
+            vec![2, 2],
+            vec![4, 3],
+        ]);
+        assert_eq!(max_points, 3);
+    }
+
+    #[test]
+    fn test_negative_slope() {
+        let max_points = max_collinear_points(vec![
+            vec![0, 0],
+            vec![1, -1],
+            vec![-1, 1],
+            vec![2, -2],
+            vec![5, 7],
+        ]);
+        assert_eq!(max_points, 4);
+    }
+
+    #[test]
+    fn test_extreme_coordinates() {
+        let max_points = max_collinear_points(vec![
+            vec![i32::MIN, i32::MIN],
+            vec![i32::MAX, i32::MAX],
+            vec![0, 0],
+            vec![-1, -1],
+            vec![i32::MAX, i32::MIN],
+            vec![i32::MIN, i32::MAX],
+            vec![0, -1],
+            vec![-1, 0],
+            vec![1, -2],
+        ]);
+        assert_eq!(max_points, 5);
+    }
+
+    #[test]
+    fn test_input_errors() {
+        let max = |rows: Vec<Vec<i32>>| try_max_collinear_points::<i8, i32>(&rows);
+        assert_eq!(max(vec![vec![1, 1], vec![2, 2], vec![-128, 127]]), Ok(2));
+        assert_eq!(max(vec![]), Err(InputError::Empty));
+        assert_eq!(
+            max(vec![vec![1, 1], vec![2]]),
+            Err(InputError::WrongArity { row: 1, len: 1 })
+        );
+        assert_eq!(
+            max(vec![vec![1, 1, 1]]),
+            Err(InputError::WrongArity { row: 0, len: 3 })
+        );
+        assert_eq!(
+            max(vec![vec![1, 1], vec![2, 2], vec![3, 300]]),
+            Err(InputError::OutOfRange {
+                row: 2,
+                column: 1,
+                value: "300".to_string()
+            })
+        );
+        assert_eq!(
+            try_max_collinear_points::<u32, i64>(&[vec![0, 0], vec![-1, 0]]),
+            Err(InputError::OutOfRange {
+                row: 1,
+                column: 0,
+                value: "-1".to_string()
+            })
+        );
+        assert_eq!(
+            points_from_rows::<BigInt, u8>(&[vec![1, 2]]),
+            Ok(vec![Point {
+                x: BigInt::from(1),
+                y: BigInt::from(2)
+            }])
+        );
+    }
+
+    #[test]
+    fn test_leetcode_wrapper() {
+        assert_eq!(max_collinear_points(vec![]), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 has 1 values, expected 2")]
+    fn test_leetcode_wrapper_rejects_short_rows() {
+        max_collinear_points(vec![vec![0, 0], vec![1]]);
+    }
+}
@@ -0,0 +1,608 @@
+//! Point lists and collinearity results as JSON, for other programs to consume.
+//!
+//! Both documents carry a `"version"` field, currently [`SCHEMA_VERSION`]:
+//!
+//! ```text
+//! {"version":1,"points":[[x,y],...]}
+//! {"version":1,"max":n,"lines":[{"a":a,"b":b,"c":c,"points":[[x,y],...]},...]}
+//! ```
+//!
+//! A line `{"a":a,"b":b,"c":c}` is `a·x + b·y = c` with the coefficients of [`Line`].
+//! Readers accept a missing version as version 1, ignore unknown fields, and keep numbers
+//! exact however many digits they have.
+
+use crate::collinear::CollinearPoints;
+use crate::geometry::{Coordinate, Line, Point};
+use crate::leetcode::{to_leetcode, Cursor, Located, Position};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The version of the documents this module reads and writes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Nesting beyond this depth is rejected rather than risking the stack.
+const MAX_DEPTH: usize = 128;
+
+/// What is wrong at the position of a [`JsonError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonErrorKind {
+    /// Something other than the described token or value was found, or the text ended.
+    Expected(&'static str),
+    /// A string contains a control character or an invalid escape.
+    InvalidString,
+    /// Arrays and objects are nested too deeply.
+    TooDeep,
+    /// The document is followed by more than whitespace.
+    TrailingCharacters,
+    /// An object lacks this required field.
+    MissingField(&'static str),
+    /// The document declares a schema version other than [`SCHEMA_VERSION`].
+    UnsupportedVersion(String),
+    /// A number does not fit the type it is read as, or is not an integer.
+    InvalidNumber(String),
+    /// The coefficients do not describe a line representable for the coordinate type.
+    InvalidLine,
+    /// A point listed for a line does not lie on it.
+    PointNotOnLine,
+}
+
+/// Why a string is not a valid document, and where.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonError {
+    /// Where the problem starts.
+    pub position: Position,
+    /// What the problem is.
+    pub kind: JsonErrorKind,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: ",
+            self.position.line, self.position.column
+        )?;
+        match &self.kind {
+            JsonErrorKind::Expected(token) => write!(f, "expected {}", token),
+            JsonErrorKind::InvalidString => write!(f, "invalid character or escape in string"),
+            JsonErrorKind::TooDeep => write!(f, "nested more than {} levels deep", MAX_DEPTH),
+            JsonErrorKind::TrailingCharacters => write!(f, "unexpected text after the document"),
+            JsonErrorKind::MissingField(name) => write!(f, "missing field {:?}", name),
+            JsonErrorKind::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported schema version {}, expected {}",
+                version, SCHEMA_VERSION
+            ),
+            JsonErrorKind::InvalidNumber(number) => write!(f, "{} is out of range", number),
+            JsonErrorKind::InvalidLine => write!(f, "coefficients do not describe a line"),
+            JsonErrorKind::PointNotOnLine => write!(f, "point does not lie on its line"),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+impl Located for JsonError {
+    type Kind = JsonErrorKind;
+
+    fn new(position: Position, kind: JsonErrorKind) -> Self {
+        JsonError { position, kind }
+    }
+}
+
+/// An error at a byte offset, turned into a [`JsonError`] once the text is at hand.
+type Failure = (usize, JsonErrorKind);
+
+enum Value {
+    /// A string, boolean or null, none of which the schemas use as a value.
+    Other,
+    /// The number as written, so that no precision is lost.
+    Number(String),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+/// A value and the byte offset where it starts.
+struct Node {
+    offset: usize,
+    value: Value,
+}
+
+struct Parser<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl Parser<'_> {
+    fn value(&mut self, depth: usize) -> Result<Node, Failure> {
+        self.cursor.skip_whitespace();
+        let offset = self.cursor.offset;
+        if depth > MAX_DEPTH {
+            return Err((offset, JsonErrorKind::TooDeep));
+        }
+        let value = if self.cursor.eat('{') {
+            let mut members = Vec::new();
+            if !self.cursor.eat('}') {
+                loop {
+                    self.cursor.skip_whitespace();
+                    let key_offset = self.cursor.offset;
+                    if !self.cursor.eat('"') {
+                        return Err((key_offset, JsonErrorKind::Expected("a string key")));
+                    }
+                    let key = self.string()?;
+                    self.cursor.expect(':', JsonErrorKind::Expected("':'"))?;
+                    members.push((key, self.value(depth + 1)?));
+                    if self.cursor.eat('}') {
+                        break;
+                    }
+                    self.cursor
+                        .expect(',', JsonErrorKind::Expected("',' or '}'"))?;
+                }
+            }
+            Value::Object(members)
+        } else if self.cursor.eat('[') {
+            let mut elements = Vec::new();
+            if !self.cursor.eat(']') {
+                loop {
+                    elements.push(self.value(depth + 1)?);
+                    if self.cursor.eat(']') {
+                        break;
+                    }
+                    self.cursor
+                        .expect(',', JsonErrorKind::Expected("',' or ']'"))?;
+                }
+            }
+            Value::Array(elements)
+        } else if self.cursor.eat('"') {
+            self.string()?;
+            Value::Other
+        } else if self.literal() {
+            Value::Other
+        } else {
+            Value::Number(self.number()?)
+        };
+        Ok(Node { offset, value })
+    }
+
+    fn literal(&mut self) -> bool {
+        match ["null", "true", "false"]
+            .into_iter()
+            .find(|word| self.cursor.rest().starts_with(word))
+        {
+            Some(word) => {
+                self.cursor.offset += word.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reads a number per the JSON grammar, keeping its text.
+    fn number(&mut self) -> Result<String, Failure> {
+        let start = self.cursor.offset;
+        let digits = |parser: &mut Self| {
+            let rest = parser.cursor.rest();
+            let len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            parser.cursor.offset += len;
+            len
+        };
+        let expect_digits = |parser: &mut Self, description| match digits(parser) {
+            0 => Err((parser.cursor.offset, JsonErrorKind::Expected(description))),
+            _ => Ok(()),
+        };
+        let minus = self.cursor.rest().starts_with('-');
+        if minus {
+            self.cursor.offset += 1;
+        }
+        if self.cursor.rest().starts_with('0') {
+            self.cursor.offset += 1;
+        } else if !minus && !self.cursor.rest().starts_with(|c: char| c.is_ascii_digit()) {
+            return Err((start, JsonErrorKind::Expected("a value")));
+        } else {
+            expect_digits(self, "a digit")?;
+        }
+        if self.cursor.rest().starts_with('.') {
+            self.cursor.offset += 1;
+            expect_digits(self, "a digit")?;
+        }
+        if self.cursor.rest().starts_with(['e', 'E']) {
+            self.cursor.offset += 1;
+            if self.cursor.rest().starts_with(['+', '-']) {
+                self.cursor.offset += 1;
+            }
+            expect_digits(self, "a digit")?;
+        }
+        Ok(self.cursor.text[start..self.cursor.offset].to_string())
+    }
+
+    /// Reads the rest of a string whose opening quote has been consumed.
+    fn string(&mut self) -> Result<String, Failure> {
+        let mut string = String::new();
+        loop {
+            let offset = self.cursor.offset;
+            let c = self
+                .cursor
+                .rest()
+                .chars()
+                .next()
+                .ok_or((offset, JsonErrorKind::Expected("'\"'")))?;
+            self.cursor.offset += c.len_utf8();
+            match c {
+                '"' => return Ok(string),
+                '\\' => string.push(
+                    self.escape()
+                        .ok_or((offset, JsonErrorKind::InvalidString))?,
+                ),
+                c if c < ' ' => return Err((offset, JsonErrorKind::InvalidString)),
+                c => string.push(c),
+            }
+        }
+    }
+
+    /// Reads the rest of an escape sequence whose backslash has been consumed.
+    fn escape(&mut self) -> Option<char> {
+        let c = self.cursor.rest().chars().next()?;
+        self.cursor.offset += c.len_utf8();
+        Some(match c {
+            '"' | '\\' | '/' => c,
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = self.hex()?;
+                if (0xd800..0xdc00).contains(&high) {
+                    self.cursor.rest().strip_prefix("\\u")?;
+                    self.cursor.offset += 2;
+                    let low = self.hex()?;
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return None;
+                    }
+                    char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))?
+                } else {
+                    char::from_u32(high)?
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    fn hex(&mut self) -> Option<u32> {
+        let digits = self.cursor.rest().get(..4)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.cursor.offset += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+}
+
+fn parse(text: &str) -> Result<Node, Failure> {
+    let mut parser = Parser {
+        cursor: Cursor::new(text, |c| matches!(c, ' ' | '\t' | '\n' | '\r')),
+    };
+    let root = parser.value(0)?;
+    parser.cursor.skip_whitespace();
+    if parser.cursor.rest().is_empty() {
+        Ok(root)
+    } else {
+        Err((parser.cursor.offset, JsonErrorKind::TrailingCharacters))
+    }
+}
+
+impl Node {
+    fn field(&self, name: &'static str) -> Result<Option<&Node>, Failure> {
+        match &self.value {
+            Value::Object(members) => Ok(members
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, node)| node)),
+            _ => Err((self.offset, JsonErrorKind::Expected("an object"))),
+        }
+    }
+
+    fn required(&self, name: &'static str) -> Result<&Node, Failure> {
+        self.field(name)?
+            .ok_or((self.offset, JsonErrorKind::MissingField(name)))
+    }
+
+    fn array(&self, description: &'static str) -> Result<&[Node], Failure> {
+        match &self.value {
+            Value::Array(elements) => Ok(elements),
+            _ => Err((self.offset, JsonErrorKind::Expected(description))),
+        }
+    }
+
+    fn integer<V: FromStr>(&self) -> Result<V, Failure> {
+        match &self.value {
+            Value::Number(number) => number
+                .parse()
+                .map_err(|_| (self.offset, JsonErrorKind::InvalidNumber(number.clone()))),
+            _ => Err((self.offset, JsonErrorKind::Expected("a number"))),
+        }
+    }
+
+    fn point<T: Coordinate>(&self) -> Result<Point<T>, Failure> {
+        match self.array("an [x, y] pair")? {
+            [x, y] => Ok(Point {
+                x: x.integer()?,
+                y: y.integer()?,
+            }),
+            _ => Err((self.offset, JsonErrorKind::Expected("an [x, y] pair"))),
+        }
+    }
+
+    fn points<T: Coordinate>(&self) -> Result<Vec<Point<T>>, Failure> {
+        self.array("an array of [x, y] pairs")?
+            .iter()
+            .map(Node::point)
+            .collect()
+    }
+
+    fn check_version(&self) -> Result<(), Failure> {
+        match self.field("version")? {
+            None => Ok(()),
+            Some(node) => match &node.value {
+                Value::Number(version) if version.parse() == Ok(SCHEMA_VERSION) => Ok(()),
+                Value::Number(version) => Err((
+                    node.offset,
+                    JsonErrorKind::UnsupportedVersion(version.clone()),
+                )),
+                _ => Err((node.offset, JsonErrorKind::Expected("a number"))),
+            },
+        }
+    }
+}
+
+/// Runs `read` on the parsed document, placing any failure in `text`.
+fn read<V>(text: &str, read: impl FnOnce(&Node) -> Result<V, Failure>) -> Result<V, JsonError> {
+    parse(text)
+        .and_then(|root| {
+            root.check_version()?;
+            read(&root)
+        })
+        .map_err(|failure| JsonError::at(text, failure))
+}
+
+/// Reads the points of a `{"points":[[x,y],...]}` document.
+pub fn points_from_json<T: Coordinate>(text: &str) -> Result<Vec<Point<T>>, JsonError> {
+    read(text, |root| root.required("points")?.points())
+}
+
+/// Writes `points` as a `{"points":[[x,y],...]}` document.
+pub fn points_to_json<T: Coordinate>(points: &[Point<T>]) -> String {
+    format!(
+        "{{\"version\":{},\"points\":{}}}",
+        SCHEMA_VERSION,
+        to_leetcode(points)
+    )
+}
+
+/// One line of a [`Report`] with the points on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportLine<T: Coordinate> {
+    /// The line.
+    pub line: Line<T>,
+    /// The points on the line, in input order.
+    pub points: Vec<Point<T>>,
+}
+
+/// The result of a collinearity query, in the form it takes as JSON.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report<T: Coordinate> {
+    /// The largest number of points on any reported line, or 0 if there are none.
+    pub max: usize,
+    /// The reported lines, in the order the query returned them.
+    pub lines: Vec<ReportLine<T>>,
+}
+
+impl<T: Coordinate> Report<T> {
+    /// The report of `lines`, which index into `points`.
+    pub fn new(points: &[Point<T>], lines: &[CollinearPoints<T>]) -> Self {
+        Report {
+            max: lines.iter().map(CollinearPoints::count).max().unwrap_or(0),
+            lines: lines
+                .iter()
+                .map(|line| ReportLine {
+                    line: line.line.clone(),
+                    points: line
+                        .indices
+                        .iter()
+                        .map(|&index| points[index].clone())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the report as a `{"max":n,"lines":[...]}` document.
+    pub fn to_json(&self) -> String {
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "{{\"a\":{},\"b\":{},\"c\":{},\"points\":{}}}",
+                    line.line.a(),
+                    line.line.b(),
+                    line.line.c(),
+                    to_leetcode(&line.points)
+                )
+            })
+            .collect();
+        format!(
+            "{{\"version\":{},\"max\":{},\"lines\":[{}]}}",
+            SCHEMA_VERSION,
+            self.max,
+            lines.join(",")
+        )
+    }
+
+    /// Reads a `{"max":n,"lines":[...]}` document, checking that every listed point lies
+    /// on its line.
+    pub fn from_json(text: &str) -> Result<Self, JsonError> {
+        read(text, |root| {
+            let lines = root
+                .required("lines")?
+                .array("an array of lines")?
+                .iter()
+                .map(|node| {
+                    let line = Line::from_coefficients(
+                        node.required("a")?.integer()?,
+                        node.required("b")?.integer()?,
+                        node.required("c")?.integer()?,
+                    )
+                    .ok_or((node.offset, JsonErrorKind::InvalidLine))?;
+                    let points_node = node.required("points")?;
+                    let points = points_node.points()?;
+                    if let Some(index) = points.iter().position(|point| !line.contains(point)) {
+                        let offset = points_node.array("")?[index].offset;
+                        return Err((offset, JsonErrorKind::PointNotOnLine));
+                    }
+                    Ok(ReportLine { line, points })
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Report {
+                max: root.required("max")?.integer()?,
+                lines,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collinear::{all_max_collinear_lines, top_k_lines, Duplicates};
+    use crate::random::SplitMix64;
+    use crate::rational::BigInt;
+    use crate::test_support::{error, fixture, random_points};
+
+    #[test]
+    fn test_points_from_json() {
+        let points = fixture("[[1,1],[3,-2]]");
+        assert_eq!(
+            points_from_json(r#"{"points":[[1,1],[3,-2]]}"#),
+            Ok(points.clone())
+        );
+        assert_eq!(
+            points_from_json(
+                " {\n  \"name\": \"cloud \\\"A\\\" \\u00e9\\ud83d\\ude00\",\n  \"tags\": [null, true, false, {}],\n  \"points\": [ [1, 1], [3, -2] ],\n  \"version\": 1, \"scale\": -0.5e+3\n}\n"
+            ),
+            Ok(points)
+        );
+        assert_eq!(points_from_json::<i32>(r#"{"points":[]}"#), Ok(vec![]));
+        assert_eq!(
+            points_from_json::<BigInt>(r#"{"points":[[123456789012345678901234567890,0]]}"#),
+            Ok(vec![Point {
+                x: "123456789012345678901234567890".parse().unwrap(),
+                y: BigInt::from(0)
+            }])
+        );
+    }
+
+    #[test]
+    fn test_json_errors() {
+        use JsonErrorKind::*;
+        let read = |text: &str| points_from_json::<i8>(text).unwrap_err();
+        assert_eq!(read(""), error(1, 1, Expected("a value")));
+        assert_eq!(read("[1,2]"), error(1, 1, Expected("an object")));
+        assert_eq!(read("{}"), error(1, 1, MissingField("points")));
+        assert_eq!(
+            read(r#"{"points":[[1,2]"#),
+            error(1, 17, Expected("',' or ']'"))
+        );
+        assert_eq!(
+            read(r#"{"points":[[1,2]]} {}"#),
+            error(1, 20, TrailingCharacters)
+        );
+        assert_eq!(read("{points:[]}"), error(1, 2, Expected("a string key")));
+        assert_eq!(
+            read(r#"{"points":[[1,2,3]]}"#),
+            error(1, 12, Expected("an [x, y] pair"))
+        );
+        assert_eq!(
+            read("{\"points\":\n  [[1,2],[3,300]]}"),
+            error(2, 13, InvalidNumber("300".to_string()))
+        );
+        assert_eq!(
+            read(r#"{"points":[[1.5,2]]}"#),
+            error(1, 13, InvalidNumber("1.5".to_string()))
+        );
+        assert_eq!(
+            read(r#"{"points":[[-,2]]}"#),
+            error(1, 14, Expected("a digit"))
+        );
+        assert_eq!(
+            read(r#"{"points":[[01,2]]}"#),
+            error(1, 14, Expected("',' or ']'"))
+        );
+        assert_eq!(read("{\"a\u{1}\":1}"), error(1, 4, InvalidString));
+        assert_eq!(read(r#"{"a":"\x"}"#), error(1, 7, InvalidString));
+        assert_eq!(read(r#"{"a":"\ud800"}"#), error(1, 7, InvalidString));
+        assert_eq!(read(r#"{"a":"open}"#), error(1, 12, Expected("'\"'")));
+        assert_eq!(
+            read(r#"{"version":2,"points":[]}"#),
+            error(1, 12, UnsupportedVersion("2".to_string()))
+        );
+        assert_eq!(read(&"[".repeat(1000)), error(1, 130, TooDeep));
+        assert_eq!(
+            read(r#"{"points":{}}"#).to_string(),
+            "line 1, column 11: expected an array of [x, y] pairs"
+        );
+    }
+
+    #[test]
+    fn test_report_json() {
+        let points = fixture("[[0,0],[1,0],[0,1],[1,1],[2,2]]");
+        let lines = all_max_collinear_lines(&points, Duplicates::Count);
+        let report = Report::new(&points, &lines);
+        assert_eq!(
+            report.to_json(),
+            r#"{"version":1,"max":3,"lines":[{"a":-1,"b":1,"c":0,"points":[[0,0],[1,1],[2,2]]}]}"#
+        );
+        assert_eq!(
+            Report::<i32>::new(&[], &[]).to_json(),
+            r#"{"version":1,"max":0,"lines":[]}"#
+        );
+        let vertical = Report::<i32>::from_json(
+            r#"{"max":2,"lines":[{"a":-2,"b":0,"c":-4,"points":[[2,5],[2,-1]]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(vertical.lines[0].line.to_string(), "x = 2");
+    }
+
+    #[test]
+    fn test_report_errors() {
+        use JsonErrorKind::*;
+        let read = |text: &str| Report::<i32>::from_json(text).unwrap_err();
+        assert_eq!(
+            read(r#"{"max":1,"lines":[{"a":0,"b":0,"c":1,"points":[]}]}"#),
+            error(1, 19, InvalidLine)
+        );
+        assert_eq!(
+            read(r#"{"max":1,"lines":[{"a":0,"b":1,"c":1,"points":[[0,1],[5,2]]}]}"#),
+            error(1, 54, PointNotOnLine)
+        );
+        assert_eq!(
+            read(r#"{"max":1,"lines":[{"a":0,"c":1,"points":[]}]}"#),
+            error(1, 19, MissingField("b"))
+        );
+        assert_eq!(read(r#"{"lines":[]}"#), error(1, 1, MissingField("max")));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut rng = SplitMix64::new(5);
+        for _ in 0..50 {
+            let points = random_points(&mut rng, 30, 6);
+            assert_eq!(
+                points_from_json(&points_to_json(&points)),
+                Ok(points.clone())
+            );
+            let report = Report::new(&points, &top_k_lines(&points, 5, Duplicates::Count));
+            assert_eq!(Report::from_json(&report.to_json()), Ok(report));
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! The `[[x,y],...]` notation LeetCode uses for point lists, which is also how test
+//! fixtures are written down.
+
+use crate::geometry::{Coordinate, Point};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// A place in the parsed text. Lines and columns are numbered from 1, and columns count
+/// characters rather than bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Position {
+    /// The line number.
+    pub line: usize,
+    /// The column within the line.
+    pub column: usize,
+}
+
+impl Position {
+    /// The position of the byte `offset` in `text`.
+    pub(crate) fn at(text: &str, offset: usize) -> Self {
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// An error of a parser that reports where in the text it failed.
+pub(crate) trait Located {
+    /// What went wrong.
+    type Kind;
+
+    /// The error of `kind` at `position`.
+    fn new(position: Position, kind: Self::Kind) -> Self;
+
+    /// The error of a failure at the byte `offset` in `text`.
+    fn at(text: &str, (offset, kind): (usize, Self::Kind)) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(Position::at(text, offset), kind)
+    }
+}
+
+/// A parser's place in its text, shared by the LeetCode and JSON readers. Failures are a
+/// byte offset and an error kind, placed with [`Located::at`] once parsing stops.
+pub(crate) struct Cursor<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) offset: usize,
+    whitespace: fn(char) -> bool,
+}
+
+impl<'a> Cursor<'a> {
+    /// A cursor at the start of `text`, which skips the characters `whitespace` accepts
+    /// between tokens.
+    pub(crate) fn new(text: &'a str, whitespace: fn(char) -> bool) -> Self {
+        Cursor {
+            text,
+            offset: 0,
+            whitespace,
+        }
+    }
+
+    pub(crate) fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start_matches(self.whitespace).len();
+    }
+
+    /// Consumes `token` after any whitespace, returning whether it was there.
+    pub(crate) fn eat(&mut self, token: char) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.offset += token.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes `token` after any whitespace, or fails with `kind` where it should be.
+    pub(crate) fn expect<K>(&mut self, token: char, kind: K) -> Result<(), (usize, K)> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err((self.offset, kind))
+        }
+    }
+}
+
+/// What went wrong at the position of a [`LeetCodeError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LeetCodeErrorKind {
+    /// Something other than the described token was found, or the text ended.
+    Expected(&'static str),
+    /// A number is not a valid value of the coordinate type.
+    InvalidCoordinate(String),
+    /// The list is followed by more than whitespace.
+    TrailingCharacters,
+}
+
+/// Why a string is not a LeetCode point list, and where.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeetCodeError {
+    /// Where the problem starts.
+    pub position: Position,
+    /// What the problem is.
+    pub kind: LeetCodeErrorKind,
+}
+
+impl fmt::Display for LeetCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: ",
+            self.position.line, self.position.column
+        )?;
+        match &self.kind {
+            LeetCodeErrorKind::Expected(token) => write!(f, "expected {}", token),
+            LeetCodeErrorKind::InvalidCoordinate(value) => {
+                write!(f, "{:?} is not a valid coordinate", value)
+            }
+            LeetCodeErrorKind::TrailingCharacters => write!(f, "unexpected text after the list"),
+        }
+    }
+}
+
+impl Error for LeetCodeError {}
+
+impl Located for LeetCodeError {
+    type Kind = LeetCodeErrorKind;
+
+    fn new(position: Position, kind: LeetCodeErrorKind) -> Self {
+        LeetCodeError { position, kind }
+    }
+}
+
+/// An error at a byte offset, turned into a [`LeetCodeError`] once parsing stops.
+type Failure = (usize, LeetCodeErrorKind);
+
+fn coordinate<T: Coordinate>(cursor: &mut Cursor) -> Result<T, Failure> {
+    cursor.skip_whitespace();
+    let rest = cursor.rest();
+    let len = rest
+        .find(|c: char| c.is_whitespace() || matches!(c, ',' | '[' | ']'))
+        .unwrap_or(rest.len());
+    if len == 0 {
+        return Err((cursor.offset, LeetCodeErrorKind::Expected("a number")));
+    }
+    let value = &rest[..len];
+    let coordinate = value.parse().map_err(|_| {
+        (
+            cursor.offset,
+            LeetCodeErrorKind::InvalidCoordinate(value.to_string()),
+        )
+    })?;
+    cursor.offset += len;
+    Ok(coordinate)
+}
+
+fn point<T: Coordinate>(cursor: &mut Cursor) -> Result<Point<T>, Failure> {
+    cursor.expect('[', LeetCodeErrorKind::Expected("'['"))?;
+    let x = coordinate(cursor)?;
+    cursor.expect(',', LeetCodeErrorKind::Expected("','"))?;
+    let y = coordinate(cursor)?;
+    cursor.expect(']', LeetCodeErrorKind::Expected("']'"))?;
+    Ok(Point { x, y })
+}
+
+fn points<T: Coordinate>(cursor: &mut Cursor) -> Result<Vec<Point<T>>, Failure> {
+    let mut points = Vec::new();
+    cursor.expect('[', LeetCodeErrorKind::Expected("'['"))?;
+    if !cursor.eat(']') {
+        loop {
+            points.push(point(cursor)?);
+            if cursor.eat(']') {
+                break;
+            }
+            cursor.expect(',', LeetCodeErrorKind::Expected("',' or ']'"))?;
+        }
+    }
+    cursor.skip_whitespace();
+    if cursor.rest().is_empty() {
+        Ok(points)
+    } else {
+        Err((cursor.offset, LeetCodeErrorKind::TrailingCharacters))
+    }
+}
+
+/// Parses a list such as `[[1,1],[3,2],[5,3]]`. Whitespace, including newlines, may
+/// appear between any two tokens.
+pub fn parse_leetcode<T: Coordinate>(text: &str) -> Result<Vec<Point<T>>, LeetCodeError> {
+    points(&mut Cursor::new(text, char::is_whitespace))
+        .map_err(|failure| LeetCodeError::at(text, failure))
+}
+
+/// Writes `points` in the compact form LeetCode prints, such as `[[1,1],[3,2]]`, which
+/// [`parse_leetcode`] reads back.
+pub fn to_leetcode<T: Display>(points: &[Point<T>]) -> String {
+    let pairs: Vec<String> = points
+        .iter()
+        .map(|point| format!("[{},{}]", point.x, point.y))
+        .collect();
+    format!("[{}]", pairs.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SplitMix64;
+    use crate::rational::BigInt;
+    use crate::test_support::{error, random_points};
+
+    #[test]
+    fn test_parse_leetcode() {
+        let points = [(1, 1), (3, -2), (5, 3)].map(|(x, y)| Point { x, y });
+        assert_eq!(parse_leetcode("[[1,1],[3,-2],[5,3]]"), Ok(points.to_vec()));
+        assert_eq!(
+            parse_leetcode(" [ [1, 1] ,\n\t[3 ,-2],[ 5,3 ] ]\n"),
+            Ok(points.to_vec())
+        );
+        assert_eq!(parse_leetcode::<i32>("[]"), Ok(vec![]));
+        assert_eq!(parse_leetcode::<i32>(" [\n] "), Ok(vec![]));
+        assert_eq!(
+            parse_leetcode::<BigInt>("[[123456789012345678901234567890,0]]"),
+            Ok(vec![Point {
+                x: "123456789012345678901234567890".parse().unwrap(),
+                y: BigInt::from(0)
+            }])
+        );
+    }
+
+    #[test]
+    fn test_parse_leetcode_errors() {
+        use LeetCodeErrorKind::*;
+        let parse = parse_leetcode::<i8>;
+        assert_eq!(parse(""), Err(error(1, 1, Expected("'['"))));
+        assert_eq!(parse("[[1,1]"), Err(error(1, 7, Expected("',' or ']'"))));
+        assert_eq!(
+            parse("[[1,1] [2,2]]"),
+            Err(error(1, 8, Expected("',' or ']'")))
+        );
+        assert_eq!(parse("[[1,1],\n  [2]]"), Err(error(2, 5, Expected("','"))));
+        assert_eq!(parse("[[1,1,1]]"), Err(error(1, 6, Expected("']'"))));
+        assert_eq!(parse("[[1,]]"), Err(error(1, 5, Expected("a number"))));
+        assert_eq!(parse("[1,1]"), Err(error(1, 2, Expected("'['"))));
+        assert_eq!(
+            parse("[[1,1],\n [·2,300]]"),
+            Err(error(2, 3, InvalidCoordinate("·2".to_string())))
+        );
+        assert_eq!(
+            parse("[[1,1],[2,300]]"),
+            Err(error(1, 11, InvalidCoordinate("300".to_string())))
+        );
+        assert_eq!(parse("[[1,1]] x"), Err(error(1, 9, TrailingCharacters)));
+        assert_eq!(
+            parse("[[1,1],[2]]").unwrap_err().to_string(),
+            "line 1, column 10: expected ','"
+        );
+    }
+
+    #[test]
+    fn test_leetcode_round_trip() {
+        let points = [(1, 1), (3, -2)].map(|(x, y)| Point { x, y });
+        assert_eq!(to_leetcode(&points), "[[1,1],[3,-2]]");
+        assert_eq!(to_leetcode::<i32>(&[]), "[]");
+        let mut rng = SplitMix64::new(7);
+        for _ in 0..100 {
+            let points = random_points(&mut rng, 20, 1000);
+            assert_eq!(parse_leetcode(&to_leetcode(&points)), Ok(points));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Exact answers to "how many of these points lie on one line?" and the questions around
+//! it: which line that is, every line tied for the maximum, the `k` richest lines, and
+//! every line with at least `k` points.
+//!
+//! Points have integer coordinates of any primitive type or [`BigInt`], and all geometry
+//! is carried out exactly, so the answers never depend on rounding or overflow.
+//!
+//! ```
+//! use max_points_on_one_line::{max_collinear_line, Duplicates, Point};
+//!
+//! let points = [(1, 1), (3, 2), (5, 3), (4, 1), (2, 3), (1, 4)].map(|(x, y)| Point { x, y });
+//! let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+//! assert_eq!(best.count(), 4);
+//! assert_eq!(best.line.to_string(), "y = -1·x + 5");
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod collinear;
+pub mod csv;
+pub mod generate;
+pub mod geometry;
+pub mod input;
+pub mod json;
+pub mod leetcode;
+pub mod parse;
+pub mod plot;
+pub mod ppm;
+pub mod random;
+pub mod rational;
+pub mod svg;
+
+#[cfg(test)]
+mod oracle;
+#[cfg(test)]
+mod test_support;
+
+pub use collinear::{
+    all_max_collinear_lines, max_collinear_count, max_collinear_line, rich_lines, top_k_lines,
+    CollinearPoints, Duplicates,
+};
+pub use csv::{read_csv, Column, CsvError, CsvErrorKind, CsvOptions, Record, Table};
+pub use generate::{generate, Instance, Workload};
+pub use geometry::{slope, Coordinate, Line, ParseLineError, ParsePointError, Point, Slope};
+pub use input::{max_collinear_points, points_from_rows, try_max_collinear_points, InputError};
+pub use json::{
+    points_from_json, points_to_json, JsonError, JsonErrorKind, Report, ReportLine, SCHEMA_VERSION,
+};
+pub use leetcode::{parse_leetcode, to_leetcode, LeetCodeError, LeetCodeErrorKind, Position};
+pub use parse::{detect_format, parse_points, parse_points_as, Format, ParseError};
+pub use plot::render_ascii;
+pub use ppm::{render_ppm, PpmOptions, Rgb};
+pub use random::SplitMix64;
+pub use rational::{BigInt, Integer, ParseBigIntError, ParseRationalError, RationalNumber};
+pub use svg::{render_svg, SvgOptions};
@@ -1,168 +1,330 @@
-use std::collections::HashMap;
+use max_points_on_one_line::{
+    generate, max_collinear_line, parse_points, render_ascii, render_ppm, render_svg, Duplicates,
+    ParseError, Point, PpmOptions, Report, SplitMix64, SvgOptions, Workload,
+};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
 
-mod rational {
-    #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-    pub struct RationalNumber {
-        numerator: i32,
-        denominator: i32,
-    }
+const USAGE: &str = "usage: max_points_on_one_line [--format plain|json|svg|plot|ppm] [FILE]...
+       max_points_on_one_line generate WORKLOAD [--seed N]
 
-    impl RationalNumber {
-        pub fn new(mut numerator: i32, mut denominator: i32) -> Self {
-            let gcd = greatest_common_divisor(numerator, denominator);
-            numerator /= gcd;
-            denominator /= gcd;
-            Self {
-                numerator,
-                denominator,
-            }
-        }
+Reads points from the files (or standard input if none, or for `-`) as whitespace-separated
+`x y` lines, `x,y` CSV, a LeetCode `[[x,y],...]` list or a JSON `{\"points\":[[x,y],...]}`
+document, and prints the maximum number of points on one line, that line, and its points,
+or an SVG drawing, a text plot or a PPM image of them. Text plots fit in $COLUMNS
+characters, or 80.
 
-        pub fn numerator(&self) -> i32 {
-            self.numerator
-        }
+`generate` prints a random JSON point list with a known answer, in its `max` field, for
+one of the workloads `planted ON_LINE NOISE` (at least 3 points on the line), `grid WIDTH
+HEIGHT`, `lattice COUNT`, `parabola COUNT` or `clusters CLUSTERS COPIES`.
 
-        pub fn denominator(&self) -> i32 {
-            self.denominator
-        }
-    }
+exit status: 0 on success, 1 if a file cannot be read, 2 on bad usage, 3 if the input is
+malformed, 4 if there are no points";
 
-    fn greatest_common_divisor(a: i32, b: i32) -> i32 {
-        if a == 0 {
-            return b;
-        } else if b == 0 {
-            return a;
-        }
-        let a = a.abs();
-        let b = b.abs();
-        let remainder = a % b;
-        greatest_common_divisor(b, remainder)
-    }
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Plain,
+    Json,
+    Svg,
+    Plot,
+    Ppm,
 }
 
-use rational::RationalNumber;
-
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
-struct Point {
-    x: i32,
-    y: i32,
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    format: OutputFormat,
+    paths: Vec<String>,
 }
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
-enum Slope {
-    Undefined,
-    Defined(RationalNumber),
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    Io(String, io::Error),
+    Parse(String, ParseError),
+    Empty,
 }
 
-fn slope(a: &Point, b: &Point) -> Slope {
-    if a.x == b.x {
-        Slope::Undefined
-    } else {
-        Slope::Defined(RationalNumber::new(a.y - b.y, a.x - b.x))
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Io(..) => 1,
+            CliError::Usage(_) => 2,
+            CliError::Parse(..) => 3,
+            CliError::Empty => 4,
+        }
     }
 }
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
-struct Line {
-    slope: Slope,
-    x_intercect: i32,
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}\n\n{}", message, USAGE),
+            CliError::Io(path, error) => write!(f, "{}: {}", path, error),
+            CliError::Parse(path, error) => write!(f, "{}: {}", path, error),
+            CliError::Empty => write!(f, "no points given"),
+        }
+    }
 }
 
-impl Line {
-    fn new(a: &Point, b: &Point) -> Self {
-        match slope(a, b) {
-            Slope::Undefined => Line {
-                slope: Slope::Undefined,
-                x_intercect: a.x,
-            },
-            Slope::Defined(slope) => Line {
-                slope: Slope::Defined(slope),
-                x_intercect: a.y - (slope.numerator() * a.x / slope.denominator()),
-            },
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, CliError> {
+    let mut format = OutputFormat::Plain;
+    let mut paths = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("plain") => OutputFormat::Plain,
+                    Some("json") => OutputFormat::Json,
+                    Some("svg") => OutputFormat::Svg,
+                    Some("plot") => OutputFormat::Plot,
+                    Some("ppm") => OutputFormat::Ppm,
+                    Some(other) => {
+                        return Err(CliError::Usage(format!("unknown format {:?}", other)))
+                    }
+                    None => return Err(CliError::Usage("--format needs a value".to_string())),
+                }
+            }
+            _ if arg.starts_with("--") => {
+                return Err(CliError::Usage(format!("unknown option {}", arg)))
+            }
+            _ => paths.push(arg),
         }
     }
+    if paths.is_empty() {
+        paths.push("-".to_string());
+    }
+    Ok(Options { format, paths })
 }
 
-fn collinear_groups(points: &[Point]) -> HashMap<Point, Vec<Point>> {
-    points
-        .iter()
-        .map(|point| (*point, collinear_group(point, points)))
-        .collect()
+fn parse_count(arg: Option<String>, name: &str) -> Result<usize, CliError> {
+    let arg = arg.ok_or_else(|| CliError::Usage(format!("missing {}", name)))?;
+    arg.parse()
+        .map_err(|_| CliError::Usage(format!("{} must be a count, not {:?}", name, arg)))
 }
 
-fn collinear_group(point: &Point, all_points: &[Point]) -> Vec<Point> {
-    let lines_containing_point = lines_containing(point, all_points);
-    match lines_containing_point
-        .iter()
-        .max_by(|p, q| p.1.len().cmp(&q.1.len()))
-    {
-        Some((_line, points)) => points.clone(),
-        None => vec![*point],
+fn parse_generate_args(
+    args: impl IntoIterator<Item = String>,
+) -> Result<(Workload, u64), CliError> {
+    let mut args = args.into_iter();
+    let workload = match args.next().as_deref() {
+        Some("planted") => {
+            let on_line = parse_count(args.next(), "ON_LINE")?;
+            if on_line < 3 {
+                return Err(CliError::Usage(
+                    "a planted line needs at least 3 points".to_string(),
+                ));
+            }
+            let noise = parse_count(args.next(), "NOISE")?;
+            Workload::PlantedLine { on_line, noise }
+        }
+        Some("grid") => Workload::Grid {
+            width: parse_count(args.next(), "WIDTH")?,
+            height: parse_count(args.next(), "HEIGHT")?,
+        },
+        Some("lattice") => Workload::LatticeLine {
+            count: parse_count(args.next(), "COUNT")?,
+        },
+        Some("parabola") => Workload::Parabola {
+            count: parse_count(args.next(), "COUNT")?,
+        },
+        Some("clusters") => Workload::ClusteredDuplicates {
+            clusters: parse_count(args.next(), "CLUSTERS")?,
+            copies: parse_count(args.next(), "COPIES")?,
+        },
+        Some(other) => return Err(CliError::Usage(format!("unknown workload {:?}", other))),
+        None => return Err(CliError::Usage("generate needs a workload".to_string())),
+    };
+    let mut seed = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliError::Usage("--seed needs a value".to_string()))?;
+                seed = value
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("invalid seed {:?}", value)))?;
+            }
+            _ => return Err(CliError::Usage(format!("unexpected argument {}", arg))),
+        }
     }
+    Ok((workload, seed))
 }
 
-fn lines_containing(point: &Point, all_points: &[Point]) -> HashMap<Line, Vec<Point>> {
-    let mut lines = HashMap::<Line, Vec<Point>>::new();
-    all_points
-        .iter()
-        .filter(|other_point| *other_point != point)
-        .for_each(|other_point| {
-            let line = Line::new(point, other_point);
-            if let Some(points) = lines.get_mut(&line) {
-                points.push(*other_point);
-            } else {
-                lines.insert(line, vec![*point, *other_point]);
-            }
-        });
-    lines
+fn read_points(path: &str) -> Result<Vec<Point<i64>>, CliError> {
+    let name = if path == "-" { "<stdin>" } else { path };
+    let text = if path == "-" {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|error| CliError::Io(name.to_string(), error))?;
+        text
+    } else {
+        std::fs::read_to_string(path).map_err(|error| CliError::Io(path.to_string(), error))?
+    };
+    parse_points(&text).map_err(|error| CliError::Parse(name.to_string(), error))
 }
 
-fn max_collinear_points(raw_points: Vec<Vec<i32>>) -> i32 {
-    let points: Vec<Point> = raw_points
-        .iter()
-        .map(|p| Point { x: p[0], y: p[1] })
-        .collect();
-    let collinear_groups = collinear_groups(&points);
-    let (_point, collinear_points) = collinear_groups
-        .iter()
-        .max_by(|p, q| p.1.len().cmp(&q.1.len()))
-        .unwrap();
-    collinear_points.len() as i32
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(80)
 }
 
-fn main() {
-    assert_eq!(
-        max_collinear_points(vec![vec![0, 0], vec![1, 1], vec![2, 2]]),
-        3
-    );
+fn render(points: &[Point<i64>], format: OutputFormat) -> Result<Vec<u8>, CliError> {
+    let best = max_collinear_line(points, Duplicates::Count).ok_or(CliError::Empty)?;
+    let members = best.indices.iter().map(|&index| &points[index]);
+    let text = match format {
+        OutputFormat::Ppm => return Ok(render_ppm(points, &[best], &PpmOptions::default())),
+        OutputFormat::Plain => {
+            let members: Vec<String> = members.map(Point::to_string).collect();
+            format!(
+                "max: {}\nline: {}\npoints: {}\n",
+                best.count(),
+                best.line,
+                members.join(" ")
+            )
+        }
+        OutputFormat::Json => format!("{}\n", Report::new(points, &[best]).to_json()),
+        OutputFormat::Svg => render_svg(points, &[best], &SvgOptions::default()),
+        OutputFormat::Plot => render_ascii(points, Some(&best), terminal_width()),
+    };
+    Ok(text.into_bytes())
+}
+
+fn run(args: impl IntoIterator<Item = String>) -> Result<Vec<u8>, CliError> {
+    let mut args = args.into_iter().peekable();
+    if args.peek().map(String::as_str) == Some("generate") {
+        let (workload, seed) = parse_generate_args(args.skip(1))?;
+        let instance = generate(workload, &mut SplitMix64::new(seed));
+        return Ok(format!("{}\n", instance.to_json()).into_bytes());
+    }
+    let options = parse_args(args)?;
+    let mut points = Vec::new();
+    for path in &options.paths {
+        points.extend(read_points(path)?);
+    }
+    render(&points, options.format)
+}
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1)) {
+        Ok(output) => match io::stdout().write_all(&output) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: <stdout>: {}", error);
+                ExitCode::from(1)
+            }
+        },
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(error.exit_code())
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(args(&[])).unwrap(),
+            Options {
+                format: OutputFormat::Plain,
+                paths: args(&["-"])
+            }
+        );
+        assert_eq!(
+            parse_args(args(&["a.txt", "--format", "json", "-"])).unwrap(),
+            Options {
+                format: OutputFormat::Json,
+                paths: args(&["a.txt", "-"])
+            }
+        );
+        for bad in [&["--format"][..], &["--format", "xml"], &["--verbose"]] {
+            assert_eq!(parse_args(args(bad)).unwrap_err().exit_code(), 2);
+        }
+    }
+
+    #[test]
+    fn test_parse_generate_args() {
+        assert_eq!(
+            parse_generate_args(args(&["planted", "5", "20", "--seed", "7"])).unwrap(),
+            (
+                Workload::PlantedLine {
+                    on_line: 5,
+                    noise: 20
+                },
+                7
+            )
+        );
+        assert_eq!(
+            parse_generate_args(args(&["grid", "3", "4"])).unwrap(),
+            (
+                Workload::Grid {
+                    width: 3,
+                    height: 4
+                },
+                0
+            )
+        );
+        for bad in [
+            &["planted", "2", "5"][..],
+            &["grid", "3"],
+            &["lattice", "-1"],
+            &["parabola", "5", "--seed"],
+            &["cube", "3"],
+            &[],
+        ] {
+            assert_eq!(parse_generate_args(args(bad)).unwrap_err().exit_code(), 2);
+        }
+    }
+
     #[test]
-    fn test_0() {
-        let max_points = max_collinear_points(vec![vec![0, 0]]);
-        assert_eq!(max_points, 1);
+    fn test_render() {
+        let points = [(0, 0), (5, 1), (1, 1), (2, 2)].map(|(x, y)| Point { x, y });
+        let text = |format| String::from_utf8(render(&points, format).unwrap()).unwrap();
+        assert_eq!(
+            text(OutputFormat::Plain),
+            "max: 3\nline: y = 1·x\npoints: (0, 0) (1, 1) (2, 2)\n"
+        );
+        assert_eq!(
+            text(OutputFormat::Json),
+            "{\"version\":1,\"max\":3,\"lines\":[{\"a\":-1,\"b\":1,\"c\":0,\"points\":[[0,0],[1,1],[2,2]]}]}\n"
+        );
+        assert!(text(OutputFormat::Svg).starts_with("<svg"));
+        assert!(render(&points, OutputFormat::Ppm)
+            .unwrap()
+            .starts_with(b"P6\n400 400\n255\n"));
+        assert_eq!(render(&[], OutputFormat::Plain).unwrap_err().exit_code(), 4);
     }
 
     #[test]
-    fn test_1() {
-        let max_points = max_collinear_points(vec![vec![1, 1], vec![2, 2], vec![3, 3]]);
-        assert_eq!(max_points, 3);
+    fn test_run_generate() {
+        let output = run(args(&["generate", "lattice", "4", "--seed", "3"])).unwrap();
+        let json = String::from_utf8(output).unwrap();
+        assert!(json.starts_with("{\"version\":1,\"max\":4,\"points\":[["));
+        let points = parse_points(&json).unwrap();
+        assert_eq!(points.len(), 4);
+        assert!(render(&points, OutputFormat::Plain)
+            .unwrap()
+            .starts_with(b"max: 4\n"));
     }
 
     #[test]
-    fn test_2() {
-        let max_points = max_collinear_points(vec![
-            vec![1, 1],
-            vec![3, 2],
-            vec![5, 3],
-            vec![4, 1],
-            vec![2, 3],
-            vec![1, 4],
-        ]);
-        assert_eq!(max_points, 4);
+    fn test_run_reports_errors() {
+        assert_eq!(
+            run(args(&["/nonexistent/points"])).unwrap_err().exit_code(),
+            1
+        );
     }
 }
@@ -0,0 +1,107 @@
+//! A deliberately naive reference answer, for checking the fast implementations against.
+
+use crate::geometry::Point;
+use crate::test_support::cross;
+
+/// The maximum number of points on one line, counting duplicates, by testing every
+/// point against the line through every pair of distinct points: `O(n³)` and
+/// independent of slopes, normalization and hashing.
+pub fn brute_force_max_points(points: &[Point<i32>]) -> usize {
+    let mut best = 0;
+    for p in points {
+        // A point with only copies of itself still lies on a line with all of them.
+        best = best.max(points.iter().filter(|q| *q == p).count());
+        for q in points.iter().filter(|q| *q != p) {
+            best = best.max(points.iter().filter(|r| cross(p, q, r) == 0).count());
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collinear::{max_collinear_count, max_collinear_line, Duplicates};
+    use crate::input::max_collinear_points;
+    use crate::random::SplitMix64;
+    use crate::test_support::{adversarial_points, fixture};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_brute_force_max_points() {
+        assert_eq!(brute_force_max_points(&[]), 0);
+        assert_eq!(brute_force_max_points(&fixture("[[4,4]]")), 1);
+        assert_eq!(brute_force_max_points(&fixture("[[4,4],[4,4],[4,4]]")), 3);
+        assert_eq!(
+            brute_force_max_points(&fixture("[[1,1],[3,2],[5,3],[4,1],[2,3],[1,4]]")),
+            4
+        );
+        assert_eq!(
+            brute_force_max_points(&fixture("[[0,0],[1,1],[0,0],[2,5],[3,3]]")),
+            4
+        );
+        let extremes = [(i32::MIN, i32::MIN), (i32::MAX, i32::MIN), (0, 0), (-1, -1)]
+            .map(|(x, y)| Point { x, y });
+        assert_eq!(brute_force_max_points(&extremes), 3);
+    }
+
+    #[test]
+    fn test_differential_against_brute_force() {
+        let mut rng = SplitMix64::new(0x5eed);
+        for case in 0..5000 {
+            let count = rng.below(16) as usize;
+            let points = adversarial_points(&mut rng, count);
+            let expected = brute_force_max_points(&points);
+            let rows = points.iter().map(|point| vec![point.x, point.y]).collect();
+            assert_eq!(
+                max_collinear_points(rows) as usize,
+                expected,
+                "case {}: {:?}",
+                case,
+                points
+            );
+            let best = max_collinear_line(&points, Duplicates::Count);
+            assert_eq!(best.map_or(0, |best| best.count()), expected);
+            assert_eq!(max_collinear_count(&points, Duplicates::Count), expected);
+
+            let mut seen = HashSet::new();
+            let distinct: Vec<Point<i32>> = points
+                .iter()
+                .copied()
+                .filter(|point| seen.insert(*point))
+                .collect();
+            let merged = max_collinear_line(&points, Duplicates::Merge);
+            assert_eq!(
+                merged.map_or(0, |best| best.count()),
+                brute_force_max_points(&distinct),
+                "case {}: {:?}",
+                case,
+                points
+            );
+            assert_eq!(
+                max_collinear_count(&points, Duplicates::Merge),
+                brute_force_max_points(&distinct)
+            );
+        }
+    }
+
+    #[test]
+    fn test_adversarial_points() {
+        let mut rng = SplitMix64::new(3);
+        let mut duplicates = false;
+        let mut extremes = false;
+        for _ in 0..100 {
+            let points = adversarial_points(&mut rng, 20);
+            assert_eq!(points.len(), 20);
+            duplicates |= (1..points.len()).any(|i| points[..i].contains(&points[i]));
+            extremes |= points
+                .iter()
+                .any(|point| point.x == i32::MIN || point.y == i32::MAX);
+        }
+        assert!(duplicates && extremes);
+        assert_eq!(
+            adversarial_points(&mut SplitMix64::new(9), 10),
+            adversarial_points(&mut SplitMix64::new(9), 10)
+        );
+    }
+}
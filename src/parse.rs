@@ -0,0 +1,257 @@
+//! Reading point lists from text in the formats people paste around: whitespace-separated
+//! columns, CSV, LeetCode's `[[x,y],...]`, and JSON `{"points":[[x,y],...]}`.
+
+use crate::csv::{read_csv, split_row, CsvError, CsvOptions};
+use crate::geometry::{Coordinate, Point};
+use crate::json::{points_from_json, JsonError};
+use crate::leetcode::{parse_leetcode, LeetCodeError};
+use std::error::Error;
+use std::fmt;
+
+/// A textual layout of a point list.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// One point per line, `x y`, separated by spaces or tabs.
+    Whitespace,
+    /// One point per row, `x,y`, optionally under a header row, as read by [`read_csv`].
+    /// Further columns are ignored.
+    Csv,
+    /// A single JSON-style array of pairs, `[[x,y],...]`.
+    LeetCode,
+    /// A JSON document with a `points` array, as read by [`points_from_json`].
+    Json,
+}
+
+/// Why a text could not be read as a point list. Lines are numbered from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// A whitespace-separated line does not have exactly two fields.
+    WrongArity {
+        /// The line number.
+        line: usize,
+        /// The number of fields on the line.
+        len: usize,
+    },
+    /// A whitespace-separated field is not a valid value of the coordinate type.
+    InvalidCoordinate {
+        /// The line number.
+        line: usize,
+        /// The field as written.
+        value: String,
+    },
+    /// The text is not a valid CSV point list.
+    Csv(CsvError),
+    /// The text is not a valid LeetCode list.
+    LeetCode(LeetCodeError),
+    /// The text is not a valid JSON point list.
+    Json(JsonError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::WrongArity { line, len } => {
+                write!(f, "line {} has {} fields, expected 2", line, len)
+            }
+            ParseError::InvalidCoordinate { line, value } => {
+                write!(f, "line {}: {:?} is not a valid coordinate", line, value)
+            }
+            ParseError::Csv(error) => write!(f, "{}", error),
+            ParseError::LeetCode(error) => write!(f, "{}", error),
+            ParseError::Json(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Guesses the format of `text`: a leading `{` means JSON, a leading `[` means LeetCode, a
+/// comma anywhere means CSV, and anything else is whitespace-separated.
+pub fn detect_format(text: &str) -> Format {
+    let start = text.trim_start();
+    if start.starts_with('{') {
+        Format::Json
+    } else if start.starts_with('[') {
+        Format::LeetCode
+    } else if text.contains(',') {
+        Format::Csv
+    } else {
+        Format::Whitespace
+    }
+}
+
+/// Reads the points in `text`, detecting its format with [`detect_format`].
+pub fn parse_points<T: Coordinate>(text: &str) -> Result<Vec<Point<T>>, ParseError> {
+    parse_points_as(text, detect_format(text))
+}
+
+/// Reads the points in `text`, which is laid out in `format`. Blank lines are ignored, and
+/// the first CSV row is skipped as a header if none of its fields looks like a number, so
+/// that a malformed first row of data is reported rather than dropped.
+pub fn parse_points_as<T: Coordinate>(
+    text: &str,
+    format: Format,
+) -> Result<Vec<Point<T>>, ParseError> {
+    match format {
+        Format::Whitespace => parse_lines(text),
+        Format::Csv => {
+            let header = text
+                .lines()
+                .find(|line| !line.trim().is_empty())
+                .and_then(|line| split_row(line, ','))
+                .is_some_and(|fields| !fields.iter().any(|field| looks_numeric(field)));
+            let options = CsvOptions {
+                header,
+                ..CsvOptions::csv(0, 1)
+            };
+            read_csv(text, &options)
+                .map(|table| table.points())
+                .map_err(ParseError::Csv)
+        }
+        Format::LeetCode => parse_leetcode(text).map_err(ParseError::LeetCode),
+        Format::Json => points_from_json(text).map_err(ParseError::Json),
+    }
+}
+
+fn looks_numeric(field: &str) -> bool {
+    field
+        .trim_start()
+        .starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'))
+}
+
+fn parse_lines<T: Coordinate>(text: &str) -> Result<Vec<Point<T>>, ParseError> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.split_whitespace().collect::<Vec<_>>()))
+        .filter(|(_, fields)| !fields.is_empty())
+        .map(|(line, fields)| match fields.as_slice() {
+            [x, y] => Ok(Point {
+                x: coordinate(line, x)?,
+                y: coordinate(line, y)?,
+            }),
+            _ => Err(ParseError::WrongArity {
+                line,
+                len: fields.len(),
+            }),
+        })
+        .collect()
+}
+
+fn coordinate<T: Coordinate>(line: usize, value: &str) -> Result<T, ParseError> {
+    value.parse().map_err(|_| ParseError::InvalidCoordinate {
+        line,
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csv::CsvErrorKind;
+
+    fn points(pairs: &[(i64, i64)]) -> Vec<Point<i64>> {
+        pairs.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format("  [[1,2]]"), Format::LeetCode);
+        assert_eq!(detect_format("\n{\"points\":[[1,2]]}"), Format::Json);
+        assert_eq!(detect_format("x,y\n1,2\n"), Format::Csv);
+        assert_eq!(detect_format("1 2\n3\t4\n"), Format::Whitespace);
+        assert_eq!(detect_format(""), Format::Whitespace);
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let expected = points(&[(1, 2), (-3, 4), (5, 6)]);
+        assert_eq!(
+            parse_points("1 2\n\n-3\t4\n  5   6  \n"),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse_points("x,y\n1,2\n-3, 4\n5 ,6\n"),
+            Ok(expected.clone())
+        );
+        assert_eq!(parse_points("1,2\r\n-3,4\r\n5,6\r\n"), Ok(expected.clone()));
+        assert_eq!(
+            parse_points("[[1,2],[-3, 4],\n [5,6]]\n"),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse_points("{\"points\": [[1,2],[-3,4],[5,6]]}"),
+            Ok(expected)
+        );
+        assert_eq!(parse_points::<i64>("[]"), Ok(vec![]));
+        assert_eq!(parse_points::<i64>("\n  \n"), Ok(vec![]));
+        assert_eq!(parse_points::<i64>("x,y\n"), Ok(vec![]));
+        // Quoted fields may hold commas, and columns after the coordinates are ignored.
+        assert_eq!(
+            parse_points("x,y,label\n1,\"2\",\"b, c\"\n\"-3\",4,d\n"),
+            Ok(points(&[(1, 2), (-3, 4)]))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_points::<i64>("1 2\n3 4 5\n"),
+            Err(ParseError::WrongArity { line: 2, len: 3 })
+        );
+        assert_eq!(
+            parse_points::<i8>("1,2\n\n3,300\n"),
+            Err(ParseError::Csv(CsvError {
+                line: 3,
+                kind: CsvErrorKind::InvalidCoordinate {
+                    column: 1,
+                    value: "300".to_string()
+                }
+            }))
+        );
+        assert_eq!(
+            parse_points::<i64>("x,y\nx,y\n"),
+            Err(ParseError::Csv(CsvError {
+                line: 2,
+                kind: CsvErrorKind::InvalidCoordinate {
+                    column: 0,
+                    value: "x".to_string()
+                }
+            }))
+        );
+        // A first row that looks numeric is data, not a header, even if it is malformed.
+        assert_eq!(
+            parse_points::<i64>("1 2\n3,4\n"),
+            Err(ParseError::Csv(CsvError {
+                line: 1,
+                kind: CsvErrorKind::InvalidCoordinate {
+                    column: 0,
+                    value: "1 2".to_string()
+                }
+            }))
+        );
+        assert_eq!(
+            parse_points::<i64>("1.5,2.5\n1,1\n2,2\n3,3\n"),
+            Err(ParseError::Csv(CsvError {
+                line: 1,
+                kind: CsvErrorKind::InvalidCoordinate {
+                    column: 0,
+                    value: "1.5".to_string()
+                }
+            }))
+        );
+        assert_eq!(
+            parse_points::<i64>("1,\"2\n").unwrap_err().to_string(),
+            "line 1: unterminated quoted field"
+        );
+        assert_eq!(
+            parse_points::<i64>("[[1,2],\n[3]]")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: expected ','"
+        );
+        assert_eq!(
+            ParseError::WrongArity { line: 2, len: 3 }.to_string(),
+            "line 2 has 3 fields, expected 2"
+        );
+    }
+}
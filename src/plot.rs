@@ -0,0 +1,157 @@
+//! Plain-text plots of small point sets, for a quick look from a terminal.
+
+use crate::collinear::CollinearPoints;
+use crate::geometry::{Coordinate, Point};
+use crate::rational::{BigInt, Integer};
+
+/// An empty cell of the grid.
+const EMPTY: char = '.';
+/// A cell holding points, none of them on the highlighted line.
+const POINT: char = 'o';
+/// A cell holding a point of the highlighted line.
+const MEMBER: char = '*';
+
+fn to_usize(value: &BigInt) -> usize {
+    value
+        .to_i64()
+        .and_then(|value| usize::try_from(value).ok())
+        .expect("cell indices are bounded by the plot width")
+}
+
+/// Draws the integer grid spanned by `points`, one character per lattice point, with `y`
+/// growing upwards. Points are marked `o`, and those of `best` `*`, whose equation is
+/// printed underneath.
+///
+/// If the grid is wider or taller than `max_width` characters, each character stands for
+/// a square of `k × k` lattice points instead, with the smallest `k` that makes it fit,
+/// and a note of the scale follows the equation. Returns an empty string for no points.
+pub fn render_ascii<T: Coordinate>(
+    points: &[Point<T>],
+    best: Option<&CollinearPoints<T>>,
+    max_width: usize,
+) -> String {
+    let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) = (
+        points.iter().map(|point| point.x.widen().to_big()).min(),
+        points.iter().map(|point| point.x.widen().to_big()).max(),
+        points.iter().map(|point| point.y.widen().to_big()).min(),
+        points.iter().map(|point| point.y.widen().to_big()).max(),
+    ) else {
+        return String::new();
+    };
+    let max_width = BigInt::from(max_width.max(1) as u64);
+    let one = BigInt::one();
+    let extent = (max_x.clone() - min_x.clone()).max(max_y.clone() - min_y.clone()) + one.clone();
+    // The smallest `k` with `ceil(extent / k) <= max_width`.
+    let scale = (extent + max_width.clone() - one.clone()) / max_width;
+    let cell = |value: BigInt, min: &BigInt| to_usize(&((value - min.clone()) / scale.clone()));
+    let width = cell(max_x, &min_x) + 1;
+    let height = cell(max_y, &min_y) + 1;
+
+    let mut grid = vec![vec![EMPTY; width]; height];
+    let members = best.map_or(&[][..], |best| &best.indices);
+    for (index, point) in points.iter().enumerate() {
+        let column = cell(point.x.widen().to_big(), &min_x);
+        let row = height - 1 - cell(point.y.widen().to_big(), &min_y);
+        let mark = &mut grid[row][column];
+        if members.binary_search(&index).is_ok() {
+            *mark = MEMBER;
+        } else if *mark == EMPTY {
+            *mark = POINT;
+        }
+    }
+
+    let mut plot = String::new();
+    for row in grid {
+        plot.extend(row);
+        plot.push('\n');
+    }
+    if let Some(best) = best {
+        let plural = if best.count() == 1 { "" } else { "s" };
+        plot += &format!("{} ({} point{})\n", best.line, best.count(), plural);
+    }
+    if scale != one {
+        plot += &format!("1 character = {0}×{0} lattice points\n", scale);
+    }
+    plot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collinear::{max_collinear_line, Duplicates};
+    use crate::rational::BigInt;
+    use crate::test_support::fixture;
+
+    #[test]
+    fn test_render_ascii() {
+        let points = fixture("[[1,1],[3,2],[5,3],[4,1],[2,3],[1,4]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(
+            render_ascii(&points, Some(&best), 80),
+            "*....
+.*..o
+..*..
+o..*.
+y = -1·x + 5 (4 points)
+"
+        );
+        assert_eq!(
+            render_ascii(&points, None, 80),
+            "o....
+.o..o
+..o..
+o..o.
+"
+        );
+    }
+
+    #[test]
+    fn test_render_ascii_negative_and_single_points() {
+        let points = fixture("[[-2,-1],[0,0],[2,1]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(
+            render_ascii(&points, Some(&best), 80),
+            "....*
+..*..
+*....
+y = 1/2·x (3 points)
+"
+        );
+        let point = fixture("[[7,-7]]");
+        let best = max_collinear_line(&point, Duplicates::Count).unwrap();
+        assert_eq!(
+            render_ascii(&point, Some(&best), 80),
+            "*\ny = -7 (1 point)\n"
+        );
+        assert_eq!(render_ascii::<i32>(&[], None, 80), "");
+    }
+
+    #[test]
+    fn test_render_ascii_downscales() {
+        // Eleven columns squeezed into four: each character covers 3×3 lattice points.
+        let points = fixture("[[0,0],[5,5],[10,10],[10,0],[1,1]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(
+            render_ascii(&points, Some(&best), 4),
+            "...*
+....
+.*..
+*..o
+y = 1·x (4 points)
+1 character = 3×3 lattice points
+"
+        );
+        let wide = [(-1_000_000_000, 3), (1_000_000_000, 3)].map(|(x, y)| Point {
+            x: BigInt::from(x),
+            y: BigInt::from(y),
+        });
+        let plot = render_ascii(&wide, None, 80);
+        assert_eq!(plot.lines().next(), Some(&*format!("o{}o", ".".repeat(78))));
+        assert_eq!(plot.lines().count(), 2);
+        let tall = [(0, i32::MIN), (0, i32::MAX)].map(|(x, y)| Point { x, y });
+        assert_eq!(
+            render_ascii(&tall, None, 2),
+            "o\no\n1 character = 2147483648×2147483648 lattice points\n"
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! Raster images of a point set and some of its lines, as binary PPM (`P6`) files, which
+//! most image tools read and convert.
+//!
+//! Everything is placed with exact integer arithmetic: the points are scaled by a whole
+//! number of pixels per lattice unit, or of lattice units per pixel, and each line is
+//! rasterized from its exact equation `a·x + b·y = c`, one run of pixels per column (or
+//! per row, for steep lines). Every member point therefore lies on its line's pixels.
+
+use crate::collinear::CollinearPoints;
+use crate::geometry::{Coordinate, Line, Point};
+use crate::rational::{div_floor, BigInt, Integer};
+use crate::svg::{point_colors, OTHER_POINTS, PALETTE};
+
+/// A color as red, green and blue intensities.
+pub type Rgb = [u8; 3];
+
+/// The size and colors of an image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PpmOptions {
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+    /// The empty border between the bounding box of the points and the image edges.
+    pub margin: u32,
+    /// The radius of the disc drawn for each point; 0 draws single pixels.
+    pub point_radius: u32,
+    /// The color of the empty image.
+    pub background: Rgb,
+    /// The color of points on none of the drawn lines.
+    pub other_points: Rgb,
+    /// The colors of the lines in turn, reused after the last.
+    pub line_colors: Vec<Rgb>,
+}
+
+impl Default for PpmOptions {
+    /// A 400×400 image in the colors of the SVG exporter.
+    fn default() -> Self {
+        PpmOptions {
+            width: 400,
+            height: 400,
+            margin: 20,
+            point_radius: 2,
+            background: [255, 255, 255],
+            other_points: OTHER_POINTS,
+            line_colors: PALETTE.to_vec(),
+        }
+    }
+}
+
+type Pixel = (i64, i64);
+
+struct Canvas {
+    width: i64,
+    height: i64,
+    pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    /// Colors the pixel at column `x` and row `y`, if it is inside the image.
+    fn set(&mut self, (x, y): Pixel, color: Rgb) {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            self.pixels[(y * self.width + x) as usize] = color;
+        }
+    }
+}
+
+fn to_i64(value: &BigInt) -> i64 {
+    value
+        .to_i64()
+        .expect("pixel positions are bounded by the image size")
+}
+
+/// `dividend / divisor` rounded down, for a divisor of either sign.
+fn floor_div(dividend: BigInt, divisor: BigInt) -> BigInt {
+    if divisor.is_negative() {
+        div_floor(-dividend, -divisor).0
+    } else {
+        div_floor(dividend, divisor).0
+    }
+}
+
+/// Where a point set lands in an image, exactly. The lattice offset `v - min` of a
+/// coordinate is scaled by `zoom / shrink`, one of which is 1, and rounded down; the
+/// bounding box is centered, with y flipped because pixels grow downwards.
+struct Frame {
+    min: (BigInt, BigInt),
+    zoom: BigInt,
+    shrink: BigInt,
+    /// The largest scaled offsets, in pixels.
+    span: (i64, i64),
+    /// The image column of the least x and the image row of the least y.
+    origin: (i64, i64),
+}
+
+impl Frame {
+    /// Fits `points` into an image of `width × height` pixels inside `margin`, as large as
+    /// a whole scale allows.
+    fn new<T: Coordinate>(points: &[Point<T>], width: u32, height: u32, margin: u32) -> Self {
+        let bounds = |coordinate: fn(&Point<T>) -> &T| {
+            let values = points
+                .iter()
+                .map(|point| coordinate(point).widen().to_big());
+            (
+                values.clone().min().unwrap_or_else(BigInt::zero),
+                values.max().unwrap_or_else(BigInt::zero),
+            )
+        };
+        let (min_x, max_x) = bounds(|point| &point.x);
+        let (min_y, max_y) = bounds(|point| &point.y);
+        let extent = (max_x.clone() - min_x.clone()).max(max_y.clone() - min_y.clone());
+        // Pixel positions run between the centers of the outermost pixels.
+        let room = |size: u32| size.saturating_sub(1).saturating_sub(2 * margin);
+        let room = BigInt::from(room(width).min(room(height)));
+        let (zoom, shrink) = if extent.is_zero() {
+            (BigInt::one(), BigInt::one())
+        } else if extent <= room {
+            (room / extent, BigInt::one())
+        } else {
+            // The least `shrink` with `extent / shrink <= room`.
+            (
+                BigInt::one(),
+                extent / (room + BigInt::one()) + BigInt::one(),
+            )
+        };
+        let scaled = |offset: BigInt| to_i64(&floor_div(offset * zoom.clone(), shrink.clone()));
+        let span = (scaled(max_x - min_x.clone()), scaled(max_y - min_y.clone()));
+        let (width, height) = (i64::from(width), i64::from(height));
+        Frame {
+            min: (min_x, min_y),
+            zoom,
+            shrink,
+            span,
+            origin: ((width - 1 - span.0) / 2, (height - 1 - span.1) / 2 + span.1),
+        }
+    }
+
+    /// The image pixel of the scaled offsets `(u, v)`.
+    fn pixel(&self, (u, v): Pixel) -> Pixel {
+        (self.origin.0 + u, self.origin.1 - v)
+    }
+
+    /// The image pixel of `point`.
+    fn locate<T: Coordinate>(&self, point: &Point<T>) -> Pixel {
+        let scaled = |value: &T, min: &BigInt| {
+            to_i64(&floor_div(
+                (value.widen().to_big() - min.clone()) * self.zoom.clone(),
+                self.shrink.clone(),
+            ))
+        };
+        self.pixel((scaled(&point.x, &self.min.0), scaled(&point.y, &self.min.1)))
+    }
+
+    /// The pixels of `line` across the bounding box, as a path of king's moves.
+    ///
+    /// For a line no steeper than 45°, each pixel column `u` stands for the x values from
+    /// `min + u·shrink / zoom` up to the next column, and is filled from the row of the
+    /// line at the first lattice x (or, when zooming in, the one exact x) in it to the
+    /// row at the last; rows are computed as `⌊(z·(c - b·min_y) - a·X) / (b·s)⌋` for
+    /// `X = z·x`. The slope keeps consecutive columns within a row of each other, and a
+    /// member point, lying between the ends of its column, is covered. Steeper lines are
+    /// drawn row by row with x and y swapped, and lines missing the box draw nothing.
+    fn rasterize<T: Coordinate>(&self, line: &Line<T>) -> Vec<Pixel> {
+        let (a, b, c) = (line.a().to_big(), line.b().to_big(), line.c().to_big());
+        let steep = a.abs() > b.abs();
+        // Along the run axis `r` and the cross axis `q`, the line is `p·r + q·s = c`.
+        let (p, q, min_run, min_cross, span_run, span_cross) = if steep {
+            (b, a, &self.min.1, &self.min.0, self.span.1, self.span.0)
+        } else {
+            (a, b, &self.min.0, &self.min.1, self.span.0, self.span.1)
+        };
+        let (zoom, shrink) = (&self.zoom, &self.shrink);
+        let constant = zoom.clone() * (c - q.clone() * min_cross.clone());
+        let divisor = q * shrink.clone();
+        // Rows beyond the box are clamped to just outside it before they are narrowed, so a
+        // line far from the points cannot overflow.
+        let (below, above) = (BigInt::from(-1), BigInt::from(span_cross + 1));
+        let cross = |scaled: BigInt| {
+            let row = floor_div(constant.clone() - p.clone() * scaled, divisor.clone());
+            to_i64(&row.max(below.clone()).min(above.clone()))
+        };
+        let mut pixels = Vec::new();
+        for run in 0..=span_run {
+            let first = min_run.clone() * zoom.clone() + BigInt::from(run) * shrink.clone();
+            let last = first.clone() + shrink.clone() - BigInt::one();
+            let (from, to) = (cross(first), cross(last));
+            let (low, high) = (from.min(to).max(0), from.max(to).min(span_cross));
+            let mut cells: Vec<i64> = (low..=high).collect();
+            if from > to {
+                cells.reverse();
+            }
+            pixels.extend(
+                cells
+                    .into_iter()
+                    .map(|cross| self.pixel(if steep { (cross, run) } else { (run, cross) })),
+            );
+        }
+        pixels
+    }
+}
+
+/// Draws `points` as discs and each of `lines` one pixel wide across the bounding box of
+/// the points, and returns the image as a binary PPM file. Members of a line take its
+/// color, from the first line that contains them.
+pub fn render_ppm<T: Coordinate>(
+    points: &[Point<T>],
+    lines: &[CollinearPoints<T>],
+    options: &PpmOptions,
+) -> Vec<u8> {
+    let frame = Frame::new(points, options.width, options.height, options.margin);
+    let mut canvas = Canvas {
+        width: i64::from(options.width),
+        height: i64::from(options.height),
+        pixels: vec![options.background; options.width as usize * options.height as usize],
+    };
+    for (line, &color) in lines.iter().zip(options.line_colors.iter().cycle()) {
+        for pixel in frame.rasterize(&line.line) {
+            canvas.set(pixel, color);
+        }
+    }
+    let colors = point_colors(
+        points.len(),
+        lines,
+        &options.line_colors,
+        options.other_points,
+    );
+    let radius = i64::from(options.point_radius);
+    for (point, &color) in points.iter().zip(&colors) {
+        let (x, y) = frame.locate(point);
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                if dx * dx + dy * dy <= radius * radius {
+                    canvas.set((x + dx, y + dy), color);
+                }
+            }
+        }
+    }
+    let mut ppm = format!("P6\n{} {}\n255\n", options.width, options.height).into_bytes();
+    ppm.extend(canvas.pixels.iter().flatten());
+    ppm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collinear::{max_collinear_line, top_k_lines, Duplicates};
+    use crate::random::SplitMix64;
+    use crate::test_support::{fixture, random_points};
+
+    const LINE: Rgb = [255, 0, 0];
+    const OTHER: Rgb = [0, 0, 255];
+
+    fn tiny(width: u32, height: u32) -> PpmOptions {
+        PpmOptions {
+            width,
+            height,
+            margin: 0,
+            point_radius: 0,
+            background: [255, 255, 255],
+            other_points: OTHER,
+            line_colors: vec![LINE],
+        }
+    }
+
+    /// The image as rows of `.` for background, `L` for line color and `o` for other
+    /// points.
+    fn sketch(ppm: &[u8], width: usize) -> Vec<String> {
+        // The header is three lines: the magic number, the size and the maximum value.
+        let body = ppm.splitn(4, |&byte| byte == b'\n').nth(3).unwrap();
+        body.chunks(3 * width)
+            .map(|row| {
+                row.chunks(3)
+                    .map(|pixel| match [pixel[0], pixel[1], pixel[2]] {
+                        LINE => 'L',
+                        OTHER => 'o',
+                        _ => '.',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_ppm() {
+        let points = fixture("[[0,0],[1,1],[2,2],[2,0]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        let ppm = render_ppm(&points, &[best], &tiny(5, 5));
+        assert!(ppm.starts_with(b"P6\n5 5\n255\n"));
+        assert_eq!(ppm.len(), b"P6\n5 5\n255\n".len() + 5 * 5 * 3);
+        assert_eq!(
+            sketch(&ppm, 5),
+            ["....L", "...L.", "..L..", ".L...", "L...o"]
+        );
+    }
+
+    #[test]
+    fn test_render_vertical_and_steep_lines() {
+        let points = fixture("[[0,0],[0,1],[0,2],[1,0],[2,2]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(best.line.to_string(), "x = 0");
+        assert_eq!(
+            sketch(&render_ppm(&points, &[best], &tiny(5, 9)), 5),
+            [".....", ".....", "L...o", "L....", "L....", "L....", "L.o..", ".....", "....."]
+        );
+        let points = fixture("[[0,0],[1,3],[2,6],[2,0]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(best.line.to_string(), "y = 3·x");
+        assert_eq!(
+            sketch(
+                &render_ppm(&points, std::slice::from_ref(&best), &tiny(7, 9)),
+                7
+            ),
+            [
+                ".......", "....L..", "...L...", "...L...", "...L...", "..L....", "..L....",
+                "..L.o..", "......."
+            ]
+        );
+        // Six lattice units in four pixels: each pixel covers two units.
+        assert_eq!(
+            sketch(&render_ppm(&points, &[best], &tiny(5, 7)), 5),
+            [".....", "..L..", ".L...", ".L...", ".Lo..", ".....", "....."]
+        );
+    }
+
+    #[test]
+    fn test_lines_pass_through_their_members() {
+        let mut rng = SplitMix64::new(21);
+        for (range, width, height) in [(3, 31, 17), (50, 97, 61), (1000, 64, 200), (9, 5, 5)] {
+            for _ in 0..20 {
+                let points = random_points(&mut rng, 25, range);
+                let frame = Frame::new(&points, width, height, 3);
+                for line in top_k_lines(&points, 6, Duplicates::Count) {
+                    let pixels = frame.rasterize(&line.line);
+                    for &index in &line.indices {
+                        assert!(pixels.contains(&frame.locate(&points[index])));
+                    }
+                    for pair in pixels.windows(2) {
+                        let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+                        assert!(dx.abs() <= 1 && dy.abs() <= 1);
+                    }
+                    assert!(pixels.iter().all(|&(x, y)| {
+                        (0..i64::from(width)).contains(&x) && (0..i64::from(height)).contains(&y)
+                    }));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lines_outside_the_points() {
+        let points = [(0i64, 0), (1, 0)].map(|(x, y)| Point { x, y });
+        let far = [
+            Line::<i64>::from_coefficients(0, 1, 10i128.pow(30)).unwrap(),
+            Line::from_coefficients(1, 0, -(10i128.pow(30))).unwrap(),
+            Line::from_coefficients(1, 1, 10i128.pow(30)).unwrap(),
+        ];
+        let frame = Frame::new(&points, 9, 9, 0);
+        for line in &far {
+            assert_eq!(frame.rasterize(line), []);
+        }
+        let lines = far.map(|line| CollinearPoints {
+            line,
+            indices: vec![],
+        });
+        assert_eq!(
+            sketch(&render_ppm(&points, &lines, &tiny(9, 9)), 9)[4],
+            "o.......o"
+        );
+    }
+
+    #[test]
+    fn test_extreme_coordinates() {
+        let points = [
+            (i64::MIN, i64::MIN),
+            (0, 0),
+            (i64::MAX, i64::MAX),
+            (i64::MAX, i64::MIN),
+        ]
+        .map(|(x, y)| Point { x, y });
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        let frame = Frame::new(&points, 9, 9, 0);
+        assert_eq!(frame.locate(&points[0]), (0, 8));
+        assert_eq!(frame.locate(&points[2]), (8, 0));
+        let pixels = frame.rasterize(&best.line);
+        assert_eq!(pixels, (0..9).map(|u| (u, 8 - u)).collect::<Vec<_>>());
+        assert_eq!(
+            sketch(&render_ppm(&points, &[best], &tiny(9, 9)), 9)[8],
+            "L.......o"
+        );
+    }
+}
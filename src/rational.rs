@@ -0,0 +1,694 @@
+//! Exact rational arithmetic over primitive and arbitrary-precision integers.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use std::str::FromStr;
+
+/// The signed integer operations a [`RationalNumber`] is built from.
+pub trait Integer:
+    Clone
+    + Debug
+    + Display
+    + FromStr
+    + Eq
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    /// The additive identity.
+    fn zero() -> Self;
+    /// The multiplicative identity.
+    fn one() -> Self;
+    /// `self + other`, or `None` on overflow.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    /// `self - other`, or `None` on overflow.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    /// `self * other`, or `None` on overflow.
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    /// `-self`, or `None` on overflow.
+    fn checked_neg(&self) -> Option<Self>;
+    /// The value as an arbitrary-precision integer.
+    fn to_big(&self) -> BigInt;
+}
+
+macro_rules! impl_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl Integer for $integer {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$integer>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$integer>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$integer>::checked_mul(*self, *other)
+                }
+
+                fn checked_neg(&self) -> Option<Self> {
+                    <$integer>::checked_neg(*self)
+                }
+
+                fn to_big(&self) -> BigInt {
+                    BigInt::from(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128);
+
+mod big;
+
+pub use big::{BigInt, ParseBigIntError};
+
+/// An exact fraction, kept in lowest terms with a positive denominator.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct RationalNumber<T> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T: Integer> RationalNumber<T> {
+    /// Builds the rational `numerator / denominator` in canonical form: reduced by the
+    /// greatest common divisor, with a positive denominator and zero stored as `0/1`.
+    ///
+    /// Panics if `denominator` is zero, or if the canonical form is not representable
+    /// in `T` (e.g. `T::MIN / -1`).
+    pub fn new(numerator: T, denominator: T) -> Self {
+        assert!(denominator != T::zero(), "denominator must be non-zero");
+        Self::canonical(numerator, denominator).expect("rational number overflows its integer type")
+    }
+
+    /// Like [`RationalNumber::new`], but returns `None` for a zero denominator or a
+    /// canonical form that is not representable in `T`.
+    pub fn try_new(numerator: T, denominator: T) -> Option<Self> {
+        if denominator == T::zero() {
+            return None;
+        }
+        Self::canonical(numerator, denominator)
+    }
+
+    /// The canonical form of `numerator / denominator`, for a non-zero denominator, or
+    /// `None` if it is not representable in `T`.
+    fn canonical(numerator: T, denominator: T) -> Option<Self> {
+        let gcd = negated_gcd(numerator.clone(), denominator.clone());
+        // The negated gcd is only unrepresentable as a positive value when it is
+        // `T::MIN`; dividing by it directly flips both signs, which is just as good.
+        let gcd = gcd.checked_neg().unwrap_or(gcd);
+        let mut numerator = numerator / gcd.clone();
+        let mut denominator = denominator / gcd;
+        if denominator < T::zero() {
+            numerator = numerator.checked_neg()?;
+            denominator = denominator.checked_neg()?;
+        }
+        Some(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// The numerator, which carries the sign.
+    pub fn numerator(&self) -> &T {
+        &self.numerator
+    }
+
+    /// The denominator, which is always positive.
+    pub fn denominator(&self) -> &T {
+        &self.denominator
+    }
+
+    /// The fraction 0/1.
+    pub fn zero() -> Self {
+        Self {
+            numerator: T::zero(),
+            denominator: T::one(),
+        }
+    }
+
+    /// The fraction 1/1.
+    pub fn one() -> Self {
+        Self {
+            numerator: T::one(),
+            denominator: T::one(),
+        }
+    }
+
+    /// Whether the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.numerator == T::zero()
+    }
+
+    /// Returns `None` if the sum overflows.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.combine(other, T::checked_add)
+    }
+
+    /// Returns `None` if the difference overflows.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.combine(other, T::checked_sub)
+    }
+
+    /// Brings both operands to the least common denominator and combines the
+    /// numerators with `operation`.
+    fn combine(&self, other: &Self, operation: fn(&T, &T) -> Option<T>) -> Option<Self> {
+        // Both denominators are positive, so their gcd can be negated back safely.
+        let gcd = -negated_gcd(self.denominator.clone(), other.denominator.clone());
+        let self_factor = other.denominator.clone() / gcd.clone();
+        let other_factor = self.denominator.clone() / gcd;
+        let numerator = operation(
+            &self.numerator.checked_mul(&self_factor)?,
+            &other.numerator.checked_mul(&other_factor)?,
+        )?;
+        let denominator = self.denominator.checked_mul(&self_factor)?;
+        Self::canonical(numerator, denominator)
+    }
+
+    /// Returns `None` if the product overflows.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        // Cancelling across the two fractions first keeps the products small.
+        let gcd_a = -negated_gcd(self.numerator.clone(), other.denominator.clone());
+        let gcd_b = -negated_gcd(other.numerator.clone(), self.denominator.clone());
+        let numerator = (self.numerator.clone() / gcd_a.clone())
+            .checked_mul(&(other.numerator.clone() / gcd_b.clone()))?;
+        let denominator =
+            (self.denominator.clone() / gcd_b).checked_mul(&(other.denominator.clone() / gcd_a))?;
+        Self::canonical(numerator, denominator)
+    }
+
+    /// Returns `None` if `other` is zero or the quotient overflows.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        self.checked_mul(&other.checked_recip()?)
+    }
+
+    /// Returns `None` if the negation overflows.
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator.clone(),
+        })
+    }
+
+    /// Returns `None` if `self` is zero or its reciprocal overflows.
+    pub fn checked_recip(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Self::canonical(self.denominator.clone(), self.numerator.clone())
+    }
+
+    /// Returns `None` if `self` is zero and `exponent` negative, or on overflow.
+    pub fn checked_pow(&self, exponent: i32) -> Option<Self> {
+        let mut base = if exponent < 0 {
+            self.checked_recip()?
+        } else {
+            self.clone()
+        };
+        let mut exponent = exponent.unsigned_abs();
+        let mut result = Self::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        assert!(!self.is_zero(), "attempt to take the reciprocal of zero");
+        self.checked_recip()
+            .expect("attempt to take the reciprocal with overflow")
+    }
+
+    /// The absolute value. Panics on overflow.
+    pub fn abs(&self) -> Self {
+        if self.numerator < T::zero() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// `-1`, `0` or `1` according to the sign of `self`.
+    pub fn signum(&self) -> Self {
+        let numerator = match self.numerator.cmp(&T::zero()) {
+            Ordering::Less => -T::one(),
+            Ordering::Equal => T::zero(),
+            Ordering::Greater => T::one(),
+        };
+        Self {
+            numerator,
+            denominator: T::one(),
+        }
+    }
+
+    /// Panics if `self` is zero and `exponent` negative, or on overflow.
+    pub fn pow(&self, exponent: i32) -> Self {
+        assert!(
+            !(self.is_zero() && exponent < 0),
+            "attempt to raise zero to a negative power"
+        );
+        self.checked_pow(exponent)
+            .expect("attempt to exponentiate with overflow")
+    }
+}
+
+/// The greatest common divisor of `a` and `b`, negated. Working with non-positive
+/// values lets `T::MIN` be reduced like any other value.
+fn negated_gcd<T: Integer>(a: T, b: T) -> T {
+    let non_positive = |value: T| if value > T::zero() { -value } else { value };
+    let (mut a, mut b) = (non_positive(a), non_positive(b));
+    while b != T::zero() {
+        if b == -T::one() {
+            // `T::MIN % -1` overflows, but the gcd is known.
+            return b;
+        }
+        let remainder = a % b.clone();
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+/// Floor division with a positive divisor, returning a remainder in `0..divisor`.
+pub(crate) fn div_floor<T: Integer>(dividend: T, divisor: T) -> (T, T) {
+    let quotient = dividend.clone() / divisor.clone();
+    let remainder = dividend % divisor.clone();
+    if remainder < T::zero() {
+        (quotient - T::one(), remainder + divisor)
+    } else {
+        (quotient, remainder)
+    }
+}
+
+impl<T: Integer> Ord for RationalNumber<T> {
+    /// Compares the continued fraction expansions of the two numbers term by term, so
+    /// that, unlike cross-multiplication, no intermediate value can overflow.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = (self.numerator.clone(), self.denominator.clone());
+        let (mut c, mut d) = (other.numerator.clone(), other.denominator.clone());
+        // Each round compares `a/b` with `c/d`, flipping the sense of the comparison
+        // whenever both sides are replaced by the reciprocals of their fractional parts.
+        let mut reversed = false;
+        loop {
+            let (q1, r1) = div_floor(a, b.clone());
+            let (q2, r2) = div_floor(c, d.clone());
+            let ordering = match (q1.cmp(&q2), r1 == T::zero(), r2 == T::zero()) {
+                (Ordering::Equal, true, true) => Ordering::Equal,
+                (Ordering::Equal, true, false) => Ordering::Less,
+                (Ordering::Equal, false, true) => Ordering::Greater,
+                (Ordering::Equal, false, false) => {
+                    (a, b, c, d) = (b, r1, d, r2);
+                    reversed = !reversed;
+                    continue;
+                }
+                (ordering, _, _) => ordering,
+            };
+            return if reversed {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+    }
+}
+
+impl<T: Integer> PartialOrd for RationalNumber<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer> fmt::Display for RationalNumber<T> {
+    /// Formats as `numerator/denominator`, or just the numerator for integers.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominator == T::one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// Why a string is not a rational of the form `n` or `n/d`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseRationalError {
+    /// The text before the `/` (or the whole text) is not an integer.
+    InvalidNumerator(String),
+    /// The text after the `/` is not an integer.
+    InvalidDenominator(String),
+    /// The denominator is zero.
+    ZeroDenominator,
+    /// The value's canonical form is not representable in the integer type.
+    Overflow,
+}
+
+impl fmt::Display for ParseRationalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRationalError::InvalidNumerator(text) => {
+                write!(f, "invalid numerator {:?}", text)
+            }
+            ParseRationalError::InvalidDenominator(text) => {
+                write!(f, "invalid denominator {:?}", text)
+            }
+            ParseRationalError::ZeroDenominator => write!(f, "denominator is zero"),
+            ParseRationalError::Overflow => write!(f, "rational number is out of range"),
+        }
+    }
+}
+
+impl Error for ParseRationalError {}
+
+impl<T: Integer> FromStr for RationalNumber<T> {
+    type Err = ParseRationalError;
+
+    /// Parses `n/d` or a plain integer `n`, allowing whitespace around each part.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numerator, denominator) = s.split_once('/').unwrap_or((s, "1"));
+        let numerator = numerator.trim();
+        let denominator = denominator.trim();
+        let numerator: T = numerator
+            .parse()
+            .map_err(|_| ParseRationalError::InvalidNumerator(numerator.to_string()))?;
+        let denominator: T = denominator
+            .parse()
+            .map_err(|_| ParseRationalError::InvalidDenominator(denominator.to_string()))?;
+        if denominator == T::zero() {
+            return Err(ParseRationalError::ZeroDenominator);
+        }
+        Self::canonical(numerator, denominator).ok_or(ParseRationalError::Overflow)
+    }
+}
+
+impl<T: Integer> Neg for RationalNumber<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+macro_rules! impl_rational_op {
+    ($($trait:ident::$method:ident, $assign_trait:ident::$assign_method:ident => $checked:ident, $message:literal;)*) => {
+        $(
+            impl<T: Integer> $trait for RationalNumber<T> {
+                type Output = Self;
+
+                fn $method(self, other: Self) -> Self {
+                    self.$checked(&other).expect($message)
+                }
+            }
+
+            impl<T: Integer> $assign_trait for RationalNumber<T> {
+                fn $assign_method(&mut self, other: Self) {
+                    *self = self.$checked(&other).expect($message);
+                }
+            }
+        )*
+    };
+}
+
+impl_rational_op! {
+    Add::add, AddAssign::add_assign => checked_add, "attempt to add with overflow";
+    Sub::sub, SubAssign::sub_assign => checked_sub, "attempt to subtract with overflow";
+    Mul::mul, MulAssign::mul_assign => checked_mul, "attempt to multiply with overflow";
+    Div::div, DivAssign::div_assign => checked_div, "attempt to divide by zero or with overflow";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SplitMix64;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of(rational: &RationalNumber<i64>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        rational.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_denominator_is_positive() {
+        let rational = RationalNumber::new(1, -2);
+        assert_eq!(*rational.numerator(), -1);
+        assert_eq!(*rational.denominator(), 2);
+    }
+
+    #[test]
+    fn test_zero_is_zero_over_one() {
+        for denominator in [-7, -1, 1, 3] {
+            let zero = RationalNumber::new(0, denominator);
+            assert_eq!((*zero.numerator(), *zero.denominator()), (0, 1));
+        }
+    }
+
+    #[test]
+    fn test_zero_denominator_is_rejected() {
+        assert_eq!(RationalNumber::try_new(3, 0), None);
+        assert_eq!(RationalNumber::try_new(0i8, 0), None);
+    }
+
+    #[test]
+    fn test_unrepresentable_rationals_are_rejected() {
+        assert_eq!(RationalNumber::try_new(i64::MIN, -1), None);
+        assert_eq!(RationalNumber::try_new(i8::MIN, -1), None);
+        let least = RationalNumber::try_new(i64::MIN, 1).unwrap();
+        assert_eq!(*least.numerator(), i64::MIN);
+        assert_eq!(
+            RationalNumber::try_new(i64::MIN, -2),
+            Some(RationalNumber::new(i64::MIN / -2, 1))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "rational number overflows its integer type")]
+    fn test_new_panics_on_overflow() {
+        RationalNumber::new(i32::MIN, -1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_panics_on_zero_denominator() {
+        RationalNumber::new(1, 0);
+    }
+
+    #[test]
+    fn test_equal_rationals_compare_and_hash_equal() {
+        let range = -12..=12;
+        let rationals: Vec<(i64, i64, RationalNumber<i64>)> = range
+            .clone()
+            .flat_map(|n| range.clone().map(move |d| (n, d)))
+            .filter(|&(_, d)| d != 0)
+            .map(|(n, d)| (n, d, RationalNumber::new(n, d)))
+            .collect();
+        for (n1, d1, r1) in &rationals {
+            for (n2, d2, r2) in &rationals {
+                let equal = n1 * d2 == n2 * d1;
+                assert_eq!(r1 == r2, equal, "{}/{} vs {}/{}", n1, d1, n2, d2);
+                if equal {
+                    assert_eq!(hash_of(r1), hash_of(r2));
+                }
+            }
+        }
+    }
+
+    fn random_rational(rng: &mut SplitMix64) -> RationalNumber<BigInt> {
+        let numerator = rng.between(-1000, 1000);
+        let denominator = rng.between(-1000, 1000).max(1);
+        RationalNumber::new(BigInt::from(numerator), BigInt::from(denominator))
+    }
+
+    #[test]
+    fn test_field_axioms_hold() {
+        let mut rng = SplitMix64::new(0x6a09_e667_f3bc_c908);
+        let zero = RationalNumber::zero();
+        let one = RationalNumber::one();
+        for _ in 0..500 {
+            let a = random_rational(&mut rng);
+            let b = random_rational(&mut rng);
+            let c = random_rational(&mut rng);
+            assert_eq!(a.clone() + b.clone(), b.clone() + a.clone());
+            assert_eq!(a.clone() * b.clone(), b.clone() * a.clone());
+            assert_eq!(
+                (a.clone() + b.clone()) + c.clone(),
+                a.clone() + (b.clone() + c.clone())
+            );
+            assert_eq!(
+                (a.clone() * b.clone()) * c.clone(),
+                a.clone() * (b.clone() * c.clone())
+            );
+            assert_eq!(
+                a.clone() * (b.clone() + c.clone()),
+                a.clone() * b.clone() + a.clone() * c.clone()
+            );
+            assert_eq!(a.clone() + zero.clone(), a);
+            assert_eq!(a.clone() * one.clone(), a);
+            assert_eq!(a.clone() + -a.clone(), zero);
+            assert_eq!(a.clone() - b.clone(), a.clone() + -b.clone());
+            if !a.is_zero() {
+                assert_eq!(a.clone() * a.recip(), one);
+                assert_eq!(b.clone() / a.clone(), b.clone() * a.recip());
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordering_matches_cross_multiplication() {
+        let mut rng = SplitMix64::new(0xbb67_ae85_84ca_a73b);
+        let extremes = [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1, i64::MAX];
+        let mut rationals: Vec<RationalNumber<i64>> = (0..200)
+            .map(|_| RationalNumber::new(rng.next_u64() as i64, (rng.next_u64() as i64).max(1)))
+            .collect();
+        for &n in &extremes {
+            for &d in extremes.iter().filter(|&&d| d > 0) {
+                rationals.push(RationalNumber::new(n, d));
+            }
+        }
+        for a in &rationals {
+            for b in &rationals {
+                let expected = (i128::from(*a.numerator()) * i128::from(*b.denominator()))
+                    .cmp(&(i128::from(*b.numerator()) * i128::from(*a.denominator())));
+                assert_eq!(a.cmp(b), expected, "{:?} <=> {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_checked_operations_detect_overflow() {
+        let max = RationalNumber::new(i8::MAX, 1);
+        let half = RationalNumber::new(1i8, 2);
+        assert_eq!(max.checked_add(&RationalNumber::one()), None);
+        assert_eq!(max.checked_mul(&RationalNumber::new(2, 1)), None);
+        assert_eq!(max.checked_div(&half), None);
+        assert_eq!(max.checked_div(&RationalNumber::zero()), None);
+        assert_eq!(RationalNumber::new(i8::MIN, 1).checked_neg(), None);
+        assert_eq!(RationalNumber::new(i8::MIN, 1).checked_recip(), None);
+        assert_eq!(half.checked_pow(8), None);
+        assert_eq!(max.checked_sub(&RationalNumber::new(i8::MIN + 1, 1)), None);
+        // Cancelling before multiplying keeps representable products in range.
+        let a = RationalNumber::new(100i8, 99);
+        let b = RationalNumber::new(99i8, 100);
+        assert_eq!(a.checked_mul(&b), Some(RationalNumber::one()));
+        assert_eq!(
+            RationalNumber::new(i8::MIN, 1).checked_sub(&RationalNumber::new(-1, 1)),
+            Some(RationalNumber::new(-127, 1))
+        );
+    }
+
+    #[test]
+    fn test_unary_operations() {
+        let a = RationalNumber::new(-2, 3);
+        assert_eq!(a.abs(), RationalNumber::new(2, 3));
+        assert_eq!(a.signum(), RationalNumber::new(-1, 1));
+        assert_eq!(
+            RationalNumber::<i32>::zero().signum(),
+            RationalNumber::zero()
+        );
+        assert_eq!(a.recip(), RationalNumber::new(-3, 2));
+        assert_eq!(a.pow(3), RationalNumber::new(-8, 27));
+        assert_eq!(a.pow(-2), RationalNumber::new(9, 4));
+        assert_eq!(a.pow(0), RationalNumber::one());
+        assert_eq!(-a, RationalNumber::new(2, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "reciprocal of zero")]
+    fn test_recip_of_zero_panics() {
+        RationalNumber::<i32>::zero().recip();
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut value = RationalNumber::new(1, 2);
+        value += RationalNumber::new(1, 3);
+        assert_eq!(value, RationalNumber::new(5, 6));
+        value -= RationalNumber::new(1, 6);
+        assert_eq!(value, RationalNumber::new(2, 3));
+        value *= RationalNumber::new(3, 4);
+        assert_eq!(value, RationalNumber::new(1, 2));
+        value /= RationalNumber::new(1, 4);
+        assert_eq!(value, RationalNumber::new(2, 1));
+    }
+
+    #[test]
+    fn test_display_and_parse_round_trip() {
+        assert_eq!(RationalNumber::new(6, -8).to_string(), "-3/4");
+        assert_eq!(RationalNumber::new(4, 2).to_string(), "2");
+        assert_eq!(RationalNumber::new(0, -5).to_string(), "0");
+        let mut rng = SplitMix64::new(0x3c6e_f372_fe94_f82b);
+        for _ in 0..500 {
+            let rational = random_rational(&mut rng);
+            assert_eq!(rational.to_string().parse(), Ok(rational));
+        }
+        assert_eq!(" 6 / -8 ".parse(), Ok(RationalNumber::new(-3, 4)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        type Parsed = Result<RationalNumber<i8>, ParseRationalError>;
+        assert_eq!(
+            "a/2".parse::<RationalNumber<i8>>(),
+            Err(ParseRationalError::InvalidNumerator("a".to_string()))
+        );
+        assert_eq!(
+            "".parse::<RationalNumber<i8>>(),
+            Err(ParseRationalError::InvalidNumerator(String::new()))
+        );
+        let parsed: Parsed = "1/2/3".parse();
+        assert_eq!(
+            parsed,
+            Err(ParseRationalError::InvalidDenominator("2/3".to_string()))
+        );
+        let parsed: Parsed = "1/0".parse();
+        assert_eq!(parsed, Err(ParseRationalError::ZeroDenominator));
+        let parsed: Parsed = "1/-128".parse();
+        assert_eq!(parsed, Err(ParseRationalError::Overflow));
+        let parsed: Parsed = "300".parse();
+        assert_eq!(
+            parsed,
+            Err(ParseRationalError::InvalidNumerator("300".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_extreme_values_are_reduced() {
+        let rational = RationalNumber::new(i64::MIN, i64::MIN);
+        assert_eq!((*rational.numerator(), *rational.denominator()), (1, 1));
+        let rational = RationalNumber::new(i64::MIN, 2);
+        assert_eq!(
+            (*rational.numerator(), *rational.denominator()),
+            (i64::MIN / 2, 1)
+        );
+
+        let rational = RationalNumber::new(i64::MIN + 1, i64::MAX);
+        assert_eq!((*rational.numerator(), *rational.denominator()), (-1, 1));
+        let rational = RationalNumber::new(i64::MAX - 1, -(i64::MAX / 2));
+        assert_eq!((*rational.numerator(), *rational.denominator()), (-2, 1));
+    }
+}
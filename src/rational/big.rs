@@ -0,0 +1,571 @@
+use super::Integer;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+/// An arbitrary-precision signed integer: a sign and a little-endian magnitude in
+/// base 2³². The magnitude never has trailing zero limbs and zero is never
+/// negative, so equal values have equal representations and hash identically.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        trim(&mut magnitude);
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    /// Whether the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    /// Whether the value is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The absolute value.
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.magnitude.clone())
+    }
+
+    /// The quotient and remainder of truncating division, matching `/` and `%`
+    /// on the primitive integers.
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "attempt to divide by zero");
+        let (quotient, remainder) = div_rem_magnitude(&self.magnitude, &other.magnitude);
+        (
+            Self::from_parts(self.negative != other.negative, quotient),
+            Self::from_parts(self.negative, remainder),
+        )
+    }
+
+    /// The value as an `i64`, or `None` if it does not fit.
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0, |high, &limb| high << 32 | u64::from(limb));
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    /// The nearest `f64`, or an infinity if the value is out of its range.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0.0, |high, &limb| high * 4_294_967_296.0 + f64::from(limb));
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// The non-negative greatest common divisor of `self` and `other`.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+}
+
+fn trim(magnitude: &mut Vec<u32>) {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+}
+
+fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0;
+    for (i, &limb) in long.iter().enumerate() {
+        let total = u64::from(limb) + u64::from(short.get(i).copied().unwrap_or(0)) + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    if carry != 0 {
+        sum.push(carry as u32);
+    }
+    sum
+}
+
+/// `a - b`, for `a >= b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut total = i64::from(limb) - i64::from(b.get(i).copied().unwrap_or(0)) - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    trim(&mut difference);
+    difference
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let total = u64::from(x) * u64::from(y) + u64::from(product[i + j]) + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    trim(&mut product);
+    product
+}
+
+/// Multiplies `magnitude` by `factor` and adds `addend`, in place.
+fn mul_add_small(magnitude: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = u64::from(addend);
+    for limb in magnitude.iter_mut() {
+        let total = u64::from(*limb) * u64::from(factor) + carry;
+        *limb = total as u32;
+        carry = total >> 32;
+    }
+    if carry != 0 {
+        magnitude.push(carry as u32);
+    }
+}
+
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare_magnitude(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if let [divisor] = *b {
+        let divisor = u64::from(divisor);
+        let mut quotient = vec![0; a.len()];
+        let mut remainder = 0;
+        for (i, &limb) in a.iter().enumerate().rev() {
+            let current = (remainder << 32) | u64::from(limb);
+            quotient[i] = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+        trim(&mut quotient);
+        let mut remainder = vec![remainder as u32];
+        trim(&mut remainder);
+        return (quotient, remainder);
+    }
+    // Binary long division: bring down one bit of `a` at a time.
+    let mut quotient = vec![0; a.len()];
+    let mut remainder = Vec::with_capacity(b.len() + 1);
+    for bit in (0..a.len() * 32).rev() {
+        let mut carry = (a[bit / 32] >> (bit % 32)) & 1;
+        for limb in remainder.iter_mut() {
+            let next = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        if carry != 0 {
+            remainder.push(carry);
+        }
+        if compare_magnitude(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(
+                self.negative,
+                add_magnitude(&self.magnitude, &other.magnitude),
+            );
+        }
+        match compare_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::from_parts(
+                other.negative,
+                sub_magnitude(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::from_parts(
+                self.negative,
+                sub_magnitude(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != other.negative,
+            mul_magnitude(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -&self
+    }
+}
+
+macro_rules! forward_binary_op {
+    ($($trait:ident::$method:ident),*) => {
+        $(
+            impl $trait for BigInt {
+                type Output = BigInt;
+
+                fn $method(self, other: BigInt) -> BigInt {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward_binary_op!(Add::add, Sub::sub, Mul::mul, Div::div, Rem::rem);
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        Self::from_parts(false, (0..4).map(|i| (value >> (32 * i)) as u32).collect())
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        Self::from_parts(value < 0, BigInt::from(value.unsigned_abs()).magnitude)
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($primitive:ty => $via:ty),*) => {
+        $(
+            impl From<$primitive> for BigInt {
+                fn from(value: $primitive) -> Self {
+                    BigInt::from(<$via>::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(
+    i8 => i128,
+    i16 => i128,
+    i32 => i128,
+    i64 => i128,
+    u8 => u128,
+    u16 => u128,
+    u32 => u128,
+    u64 => u128
+);
+
+/// Why a string is not a decimal integer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    /// The string has no digits.
+    Empty,
+    /// The character at this byte offset is not a decimal digit.
+    InvalidDigit(usize),
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit(position) => {
+                write!(f, "invalid digit at byte {}", position)
+            }
+        }
+    }
+}
+
+impl Error for ParseBigIntError {}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits_start) = match s.as_bytes().first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+        if s.len() == digits_start {
+            return Err(ParseBigIntError::Empty);
+        }
+        let mut magnitude = Vec::new();
+        for (position, byte) in s.bytes().enumerate().skip(digits_start) {
+            if !byte.is_ascii_digit() {
+                return Err(ParseBigIntError::InvalidDigit(position));
+            }
+            mul_add_small(&mut magnitude, 10, u32::from(byte - b'0'));
+        }
+        Ok(Self::from_parts(negative, magnitude))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+        let mut chunks = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = div_rem_magnitude(&magnitude, &[CHUNK]);
+            chunks.push(remainder.first().copied().unwrap_or(0));
+            magnitude = quotient;
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+impl Integer for BigInt {
+    fn zero() -> Self {
+        Self::from_parts(false, Vec::new())
+    }
+
+    fn one() -> Self {
+        Self::from_parts(false, vec![1])
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_neg(&self) -> Option<Self> {
+        Some(-self)
+    }
+
+    fn to_big(&self) -> BigInt {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SplitMix64;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn big(value: i128) -> BigInt {
+        BigInt::from(value)
+    }
+
+    fn random_i64(rng: &mut SplitMix64) -> i128 {
+        let value = rng.next_u64() as i64;
+        // Bias towards small magnitudes and single-limb values as well.
+        i128::from(match rng.next_u64() % 3 {
+            0 => value,
+            1 => value >> 32,
+            _ => value >> 56,
+        })
+    }
+
+    #[test]
+    fn test_arithmetic_matches_i128() {
+        let mut rng = SplitMix64::new(0x853c_49e6_748f_ea9b);
+        for _ in 0..5000 {
+            let (a, b) = (random_i64(&mut rng), random_i64(&mut rng));
+            assert_eq!(big(a) + big(b), big(a + b), "{} + {}", a, b);
+            assert_eq!(big(a) - big(b), big(a - b), "{} - {}", a, b);
+            assert_eq!(big(a) * big(b), big(a * b), "{} * {}", a, b);
+            assert_eq!(big(a).cmp(&big(b)), a.cmp(&b), "{} <=> {}", a, b);
+            if b != 0 {
+                assert_eq!(big(a) / big(b), big(a / b), "{} / {}", a, b);
+                assert_eq!(big(a) % big(b), big(a % b), "{} % {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multi_limb_division_round_trips() {
+        let mut rng = SplitMix64::new(0xda3e_39cb_94b9_5bdb);
+        for _ in 0..500 {
+            let a =
+                big(random_i64(&mut rng)) * big(random_i64(&mut rng)) * big(random_i64(&mut rng));
+            let b = big(random_i64(&mut rng)) * big(random_i64(&mut rng));
+            if b.is_zero() {
+                continue;
+            }
+            let (quotient, remainder) = a.div_rem(&b);
+            assert_eq!(&(&quotient * &b) + &remainder, a);
+            assert!(remainder.abs() < b.abs());
+            assert!(remainder.is_zero() || remainder.is_negative() == a.is_negative());
+        }
+    }
+
+    #[test]
+    fn test_gcd_is_non_negative() {
+        assert_eq!(big(-12).gcd(&big(18)), big(6));
+        assert_eq!(big(0).gcd(&big(-5)), big(5));
+        assert_eq!(big(0).gcd(&big(0)), big(0));
+    }
+
+    #[test]
+    fn test_zero_has_a_single_representation() {
+        let zero = big(12345) - big(12345);
+        let negated = -zero.clone();
+        assert_eq!(zero, BigInt::zero());
+        assert_eq!(negated, BigInt::zero());
+        assert!(!negated.is_negative());
+        let hash = |value: &BigInt| {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&zero), hash(&negated));
+        assert_eq!(hash(&(big(1 << 40) - big(1 << 39))), hash(&big(1 << 39)));
+    }
+
+    #[test]
+    fn test_decimal_round_trip() {
+        for text in [
+            "0",
+            "-1",
+            "1000000000",
+            "-4294967296",
+            "170141183460469231731687303715884105727",
+            "-31415926535897932384626433832795028841971693993751",
+        ] {
+            assert_eq!(text.parse::<BigInt>().unwrap().to_string(), text);
+        }
+        assert_eq!("+007".parse::<BigInt>().unwrap(), big(7));
+        assert_eq!(i128::MIN.to_string(), big(i128::MIN).to_string());
+    }
+
+    #[test]
+    fn test_primitive_conversions() {
+        for value in [0, 1, -1, i64::MAX, i64::MIN, 1 << 32, -(1 << 32) - 7] {
+            assert_eq!(big(i128::from(value)).to_i64(), Some(value));
+            assert_eq!(big(i128::from(value)).to_f64(), value as f64);
+        }
+        for value in [
+            i128::from(i64::MAX) + 1,
+            i128::from(i64::MIN) - 1,
+            i128::MIN,
+        ] {
+            assert_eq!(big(value).to_i64(), None);
+            assert_eq!(big(value).to_f64(), value as f64);
+        }
+        assert_eq!(big(-1).to_big(), big(-1));
+        assert_eq!((-7i8).to_big(), big(-7));
+        let huge: BigInt = format!("1{}", "0".repeat(400)).parse().unwrap();
+        assert_eq!(huge.to_f64(), f64::INFINITY);
+        assert_eq!((-huge).to_f64(), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+        assert_eq!(
+            "12a4".parse::<BigInt>(),
+            Err(ParseBigIntError::InvalidDigit(2))
+        );
+        assert_eq!(
+            "--1".parse::<BigInt>(),
+            Err(ParseBigIntError::InvalidDigit(1))
+        );
+    }
+}
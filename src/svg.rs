@@ -0,0 +1,291 @@
+//! SVG drawings of a point set and some of its lines, for reviewing results by eye.
+//!
+//! The output depends only on the inputs: coordinates are printed with two decimals and
+//! elements appear in a fixed order (lines, then points, then labels), so drawings can be
+//! compared against golden files.
+
+use crate::collinear::CollinearPoints;
+use crate::geometry::{Coordinate, Line, Point};
+use crate::rational::Integer;
+
+/// The colors of the first lines drawn, reused in turn after the last, as red, green and
+/// blue intensities. The PPM writer draws with the same ones.
+pub(crate) const PALETTE: [[u8; 3]; 6] = [
+    [214, 39, 40],
+    [31, 119, 180],
+    [44, 160, 44],
+    [255, 127, 14],
+    [148, 103, 189],
+    [140, 86, 75],
+];
+
+/// The color of points on none of the drawn lines.
+pub(crate) const OTHER_POINTS: [u8; 3] = [153, 153, 153];
+
+/// The size and spacing of a drawing, in pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// The width of the image.
+    pub width: u32,
+    /// The height of the image.
+    pub height: u32,
+    /// The empty border between the bounding box of the points and the image edges.
+    pub margin: u32,
+    /// The radius of the dot drawn for each point.
+    pub point_radius: u32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            width: 400,
+            height: 400,
+            margin: 20,
+            point_radius: 3,
+        }
+    }
+}
+
+/// An approximation for drawing.
+fn approximate<W: Integer>(value: &W) -> f64 {
+    value.to_big().to_f64()
+}
+
+/// Prints a color as `#rrggbb`.
+fn hex([red, green, blue]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
+
+/// Prints a pixel coordinate, without the `-0.00` that rounding can leave.
+fn pixel(value: f64) -> String {
+    format!("{:.2}", (value * 100.0).round() / 100.0 + 0.0)
+}
+
+/// Where a point set lands in an image: its bounding box, scaled uniformly to fit inside
+/// the margins and centered, with y flipped because pixels grow downwards.
+struct Frame {
+    /// The approximate coordinates of the points, in input order.
+    coordinates: Vec<(f64, f64)>,
+    min: (f64, f64),
+    max: (f64, f64),
+    scale: f64,
+    offset: (f64, f64),
+    height: f64,
+}
+
+impl Frame {
+    fn new<T: Coordinate>(points: &[Point<T>], width: u32, height: u32, margin: u32) -> Self {
+        let coordinates: Vec<(f64, f64)> = points
+            .iter()
+            .map(|point| (approximate(&point.x.widen()), approximate(&point.y.widen())))
+            .collect();
+        let bound = |select: fn(&(f64, f64)) -> f64| {
+            let values = coordinates.iter().map(select);
+            let min = values.clone().fold(f64::INFINITY, f64::min);
+            let max = values.fold(f64::NEG_INFINITY, f64::max);
+            match (min, max) {
+                _ if coordinates.is_empty() => (-1.0, 1.0),
+                // A box without area would leave nothing to scale by.
+                _ if min == max => (min - 1.0, max + 1.0),
+                bounds => bounds,
+            }
+        };
+        let (min_x, max_x) = bound(|&(x, _)| x);
+        let (min_y, max_y) = bound(|&(_, y)| y);
+        let (width, height, margin) = (f64::from(width), f64::from(height), f64::from(margin));
+        let scale = ((width - 2.0 * margin) / (max_x - min_x))
+            .min((height - 2.0 * margin) / (max_y - min_y));
+        Frame {
+            coordinates,
+            min: (min_x, min_y),
+            max: (max_x, max_y),
+            scale,
+            offset: (
+                (width - scale * (max_x - min_x)) / 2.0,
+                (height - scale * (max_y - min_y)) / 2.0,
+            ),
+            height,
+        }
+    }
+
+    /// The image position of `(x, y)`.
+    fn to_pixels(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.offset.0 + self.scale * (x - self.min.0),
+            self.height - self.offset.1 - self.scale * (y - self.min.1),
+        )
+    }
+
+    /// The part of `line` inside the bounding box, as its two ends ordered by `(x, y)`.
+    fn clip<T: Coordinate>(&self, line: &Line<T>) -> Option<((f64, f64), (f64, f64))> {
+        let (min, max) = (self.min, self.max);
+        let (a, b, c) = (
+            approximate(line.a()),
+            approximate(line.b()),
+            approximate(line.c()),
+        );
+        // Intersections with the two vertical edges, then with the two horizontal ones; a
+        // vertical line has none of the former and a horizontal line none of the latter.
+        let mut ends = Vec::new();
+        if b != 0.0 {
+            ends.extend([min.0, max.0].map(|x| (x, (c - a * x) / b)));
+        }
+        if a != 0.0 {
+            ends.extend([min.1, max.1].map(|y| ((c - b * y) / a, y)));
+        }
+        let tolerance = 1e-9 * (max.0 - min.0).max(max.1 - min.1);
+        ends.retain(|&(x, y)| {
+            (min.0 - tolerance..=max.0 + tolerance).contains(&x)
+                && (min.1 - tolerance..=max.1 + tolerance).contains(&y)
+        });
+        ends.sort_by(|p, q| p.partial_cmp(q).expect("intersections are finite"));
+        Some((*ends.first()?, *ends.last()?))
+    }
+}
+
+/// The color of each of `count` points: that of the first of `lines` containing it, with
+/// the lines colored from `palette` in turn, or `other` if no line does.
+pub(crate) fn point_colors<T: Coordinate, C: Copy>(
+    count: usize,
+    lines: &[CollinearPoints<T>],
+    palette: &[C],
+    other: C,
+) -> Vec<C> {
+    let mut colors = vec![None; count];
+    for (line, &color) in lines.iter().zip(palette.iter().cycle()) {
+        for &index in &line.indices {
+            colors[index].get_or_insert(color);
+        }
+    }
+    colors
+        .into_iter()
+        .map(|color| color.unwrap_or(other))
+        .collect()
+}
+
+/// Draws `points` as dots and each of `lines` across the bounding box of the points, with
+/// its number of points written at its middle. Members of a line take its color, from
+/// the first line that contains them; other points are gray.
+pub fn render_svg<T: Coordinate>(
+    points: &[Point<T>],
+    lines: &[CollinearPoints<T>],
+    options: &SvgOptions,
+) -> String {
+    let frame = Frame::new(points, options.width, options.height, options.margin);
+    let colors = point_colors(points.len(), lines, &PALETTE, OTHER_POINTS);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n<rect width=\"{0}\" height=\"{1}\" fill=\"white\"/>\n",
+        options.width, options.height
+    );
+    let mut labels = String::new();
+    for (line, &color) in lines.iter().zip(PALETTE.iter().cycle()) {
+        let Some((start, end)) = frame.clip(&line.line) else {
+            continue;
+        };
+        let (start, end) = (frame.to_pixels(start), frame.to_pixels(end));
+        svg += &format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+            pixel(start.0),
+            pixel(start.1),
+            pixel(end.0),
+            pixel(end.1),
+            hex(color)
+        );
+        labels += &format!(
+            "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-family=\"sans-serif\" font-size=\"12\">{} points</text>\n",
+            pixel((start.0 + end.0) / 2.0 + 4.0),
+            pixel((start.1 + end.1) / 2.0 - 4.0),
+            hex(color),
+            line.count()
+        );
+    }
+    for (&coordinates, &color) in frame.coordinates.iter().zip(&colors) {
+        let (x, y) = frame.to_pixels(coordinates);
+        svg += &format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+            pixel(x),
+            pixel(y),
+            options.point_radius,
+            hex(color)
+        );
+    }
+    svg + &labels + "</svg>\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collinear::{max_collinear_line, top_k_lines, Duplicates};
+    use crate::test_support::fixture;
+
+    const SMALL: SvgOptions = SvgOptions {
+        width: 100,
+        height: 100,
+        margin: 10,
+        point_radius: 2,
+    };
+
+    #[test]
+    fn test_render_best_line() {
+        let points = fixture("[[0,0],[1,1],[2,2],[2,0]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        assert_eq!(
+            render_svg(&points, &[best], &SMALL),
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100" viewBox="0 0 100 100">
+<rect width="100" height="100" fill="white"/>
+<line x1="10.00" y1="90.00" x2="90.00" y2="10.00" stroke="#d62728" stroke-width="2"/>
+<circle cx="10.00" cy="90.00" r="2" fill="#d62728"/>
+<circle cx="50.00" cy="50.00" r="2" fill="#d62728"/>
+<circle cx="90.00" cy="10.00" r="2" fill="#d62728"/>
+<circle cx="90.00" cy="90.00" r="2" fill="#999999"/>
+<text x="54.00" y="46.00" fill="#d62728" font-family="sans-serif" font-size="12">3 points</text>
+</svg>
+"##
+        );
+    }
+
+    #[test]
+    fn test_render_vertical_and_steep_lines() {
+        // Lines of slope 4 and -4 and a vertical one, in a box four times as tall as it
+        // is wide.
+        let points = fixture("[[0,0],[0,4],[0,8],[1,4],[2,8],[2,0]]");
+        let lines = top_k_lines(&points, 3, Duplicates::Count);
+        assert_eq!(
+            lines
+                .iter()
+                .map(|line| line.line.to_string())
+                .collect::<Vec<_>>(),
+            ["y = 4·x", "x = 0", "y = -4·x + 8"]
+        );
+        let svg = render_svg(&points, &lines, &SMALL);
+        let drawn: Vec<&str> = svg
+            .lines()
+            .filter(|line| line.starts_with("<line"))
+            .collect();
+        assert_eq!(
+            drawn,
+            [
+                r##"<line x1="40.00" y1="90.00" x2="60.00" y2="10.00" stroke="#d62728" stroke-width="2"/>"##,
+                r##"<line x1="40.00" y1="90.00" x2="40.00" y2="10.00" stroke="#1f77b4" stroke-width="2"/>"##,
+                r##"<line x1="40.00" y1="10.00" x2="60.00" y2="90.00" stroke="#2ca02c" stroke-width="2"/>"##,
+            ]
+        );
+        // (0, 0) is on the first two lines and keeps the color of the first.
+        assert!(svg.contains(r##"<circle cx="40.00" cy="90.00" r="2" fill="#d62728"/>"##));
+        assert!(svg.contains(r##"<circle cx="60.00" cy="90.00" r="2" fill="#2ca02c"/>"##));
+        assert_eq!(svg.matches(">3 points</text>").count(), 3);
+        assert_eq!(svg, render_svg(&points, &lines, &SMALL));
+    }
+
+    #[test]
+    fn test_render_degenerate_sets() {
+        let empty = render_svg::<i32>(&[], &[], &SvgOptions::default());
+        assert!(empty.ends_with("<rect width=\"400\" height=\"400\" fill=\"white\"/>\n</svg>\n"));
+        let points = fixture("[[3,3],[3,3]]");
+        let best = max_collinear_line(&points, Duplicates::Count).unwrap();
+        let svg = render_svg(&points, &[best], &SMALL);
+        assert!(svg.contains(r##"<line x1="10.00" y1="50.00" x2="90.00" y2="50.00""##));
+        assert!(svg.contains(r##"<circle cx="50.00" cy="50.00" r="2" fill="#d62728"/>"##));
+        assert!(svg.contains(">2 points</text>"));
+    }
+}
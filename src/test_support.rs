@@ -0,0 +1,114 @@
+use crate::geometry::Point;
+use crate::leetcode::{parse_leetcode, Located, Position};
+use crate::random::SplitMix64;
+use std::collections::HashSet;
+
+pub fn coordinate(rng: &mut SplitMix64, range: i32) -> i32 {
+    rng.between(-i64::from(range), i64::from(range)) as i32
+}
+
+pub fn random_points(rng: &mut SplitMix64, count: usize, range: i32) -> Vec<Point<i32>> {
+    (0..count)
+        .map(|_| Point {
+            x: coordinate(rng, range),
+            y: coordinate(rng, range),
+        })
+        .collect()
+}
+
+pub fn distinct_random_points(rng: &mut SplitMix64, count: usize, range: i32) -> Vec<Point<i32>> {
+    let mut seen = HashSet::new();
+    let mut points = random_points(rng, count, range);
+    points.retain(|point| seen.insert(*point));
+    points
+}
+
+/// Coordinates at and around the edges of the `i32` range, where overflow hides.
+const EXTREMES: [i32; 9] = [
+    i32::MIN,
+    i32::MIN + 1,
+    i32::MIN / 2,
+    -1,
+    0,
+    1,
+    i32::MAX / 2,
+    i32::MAX - 1,
+    i32::MAX,
+];
+
+/// `count` points, skewed towards the cases that break line counting: a planted
+/// line of random (often negative or steep) slope, a vertical line, a dense small grid,
+/// extreme coordinates, and exact duplicates of earlier points.
+pub fn adversarial_points(rng: &mut SplitMix64, count: usize) -> Vec<Point<i32>> {
+    let origin = (
+        i64::from(*rng.choose(&EXTREMES)) * rng.below(2) as i64,
+        i64::from(*rng.choose(&EXTREMES)) * rng.below(2) as i64,
+    );
+    let direction = (rng.between(0, 3), rng.between(-3, 3));
+    let direction = if direction == (0, 0) {
+        (0, 1)
+    } else {
+        direction
+    };
+    let vertical_x = rng.between(-2, 2) as i32;
+    let mut points: Vec<Point<i32>> = Vec::with_capacity(count);
+    for _ in 0..count {
+        let point = match rng.below(6) {
+            0 => {
+                let t = rng.between(-6, 6);
+                let on_line = (origin.0 + t * direction.0, origin.1 + t * direction.1);
+                match (i32::try_from(on_line.0), i32::try_from(on_line.1)) {
+                    (Ok(x), Ok(y)) => Point { x, y },
+                    // The line runs off the coordinate range; take its origin instead.
+                    _ => Point {
+                        x: origin.0 as i32,
+                        y: origin.1 as i32,
+                    },
+                }
+            }
+            1 => Point {
+                x: vertical_x,
+                y: rng.between(-4, 4) as i32,
+            },
+            2 => Point {
+                x: rng.between(-3, 3) as i32,
+                y: rng.between(-3, 3) as i32,
+            },
+            3 => Point {
+                x: *rng.choose(&EXTREMES),
+                y: *rng.choose(&EXTREMES),
+            },
+            4 if !points.is_empty() => *rng.choose(&points),
+            _ => Point {
+                x: rng.between(i64::from(i32::MIN), i64::from(i32::MAX)) as i32,
+                y: rng.between(i64::from(i32::MIN), i64::from(i32::MAX)) as i32,
+            },
+        };
+        points.push(point);
+    }
+    points
+}
+
+/// Twice the signed area of the triangle `o`, `a`, `b`, which is zero exactly when the
+/// three points are collinear.
+pub fn cross(o: &Point<i32>, a: &Point<i32>, b: &Point<i32>) -> i128 {
+    let (ax, ay) = (
+        i128::from(a.x) - i128::from(o.x),
+        i128::from(a.y) - i128::from(o.y),
+    );
+    let (bx, by) = (
+        i128::from(b.x) - i128::from(o.x),
+        i128::from(b.y) - i128::from(o.y),
+    );
+    ax * by - ay * bx
+}
+
+/// The points of a fixture written in LeetCode notation, such as `[[1,1],[3,2]]`.
+pub fn fixture(text: &str) -> Vec<Point<i32>> {
+    parse_leetcode(text).expect("malformed fixture")
+}
+
+/// The error a parser reports at `line` and `column`.
+pub fn error<E: Located>(line: usize, column: usize, kind: E::Kind) -> E {
+    E::new(Position { line, column }, kind)
+}